@@ -0,0 +1,192 @@
+use bevy::{app::AppExit, prelude::*};
+use die_ursuppe::{
+    brain::MutationSettings,
+    sim::{
+        self, EnvironmentSchedule, ImportChunkSettings, LoadObstacles, RunTicks, Save,
+        SimulationRng, SimulationSettings, SpawnCell, TickCount,
+    },
+    SimulationPlugin,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::de::DeserializeOwned;
+use std::{env, fs, process, str::FromStr};
+
+const USAGE: &str = "Usage: die_ursuppe-headless <settings file> <tick count> <save name> \
+[--cells <cell count>] [--cell-energy <cell energy>] [--mutation-settings <mutation settings file>] \
+[--obstacles <obstacle file>] [--chunk-settings <chunk settings image>] \
+[--environment <environment schedule file>]";
+
+/// Parameter des Headless-Laufs (aus der Kommandozeile)
+#[derive(Resource)]
+struct HeadlessRun {
+    /// Nach wie vielen Ticks gespeichert und beendet wird
+    tick_count: u64,
+    save_name: String,
+    /// Anzahl an Zellen, die zu Beginn gespawned werden
+    cell_count: u32,
+    /// Start-Energie der zu Beginn gespawnten Zellen
+    cell_energy: f32,
+    /// RON-Datei mit Hindernissen, die zu Beginn geladen werden
+    obstacle_file: Option<String>,
+    /// Bild mit den Chunk Settings, das zu Beginn importiert wird
+    chunk_settings_image: Option<String>,
+}
+
+fn main() {
+    // Kommandozeile in Positions-Argumente und Optionen aufteilen
+    let mut positional_arguments = Vec::new();
+    let mut cell_count = 50;
+    let mut cell_energy = 199.;
+    let mut mutation_settings = MutationSettings::default();
+    let mut environment_schedule = EnvironmentSchedule::default();
+    let mut obstacle_file = None;
+    let mut chunk_settings_image = None;
+    let mut arguments = env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--cells" => cell_count = parse_argument(arguments.next()),
+            "--cell-energy" => cell_energy = parse_argument(arguments.next()),
+            "--mutation-settings" => {
+                mutation_settings =
+                    read_ron_file(&arguments.next().unwrap_or_else(|| exit_with_usage()))
+            }
+            "--obstacles" => {
+                obstacle_file = Some(arguments.next().unwrap_or_else(|| exit_with_usage()))
+            }
+            "--environment" => {
+                environment_schedule =
+                    read_ron_file(&arguments.next().unwrap_or_else(|| exit_with_usage()))
+            }
+            "--chunk-settings" => {
+                chunk_settings_image = Some(arguments.next().unwrap_or_else(|| exit_with_usage()))
+            }
+            _ => positional_arguments.push(argument),
+        }
+    }
+    if positional_arguments.len() != 3 {
+        exit_with_usage();
+    }
+    let headless_run = HeadlessRun {
+        tick_count: parse_argument(Some(positional_arguments[1].clone())),
+        save_name: positional_arguments[2].clone(),
+        cell_count,
+        cell_energy,
+        obstacle_file,
+        chunk_settings_image,
+    };
+
+    // Simulation-Settings laden
+    let mut simulation_settings: SimulationSettings = read_ron_file(&positional_arguments[0]);
+    // Ohne Fenster soll so schnell wie möglich getickt werden.
+    // Gestartet wird über `RunTicks`, damit nach genau `tick_count` Ticks pausiert wird.
+    simulation_settings.tick_delta_seconds = 0.;
+    simulation_settings.is_paused = true;
+    let simulation_rng = SimulationRng(ChaCha12Rng::seed_from_u64(simulation_settings.seed));
+
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .insert_resource(simulation_settings)
+        .insert_resource(mutation_settings)
+        .insert_resource(environment_schedule)
+        .insert_resource(simulation_rng)
+        .insert_resource(headless_run)
+        .add_plugin(SimulationPlugin)
+        .add_startup_system(start_run)
+        .add_system(finish_run.before(sim::save))
+        .run();
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Parsed ein Kommandozeilen-Argument oder beendet das Programm mit der Usage
+fn parse_argument<T: FromStr>(argument: Option<String>) -> T {
+    argument
+        .and_then(|argument| argument.parse().ok())
+        .unwrap_or_else(|| exit_with_usage())
+}
+
+/// Liest eine RON-Datei oder beendet das Programm mit einer Fehlermeldung
+fn read_ron_file<T: DeserializeOwned>(path: &str) -> T {
+    let serialized = fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("Could not read file {}: {}", path, error);
+        process::exit(1);
+    });
+    ron::from_str(&serialized).unwrap_or_else(|error| {
+        eprintln!("Could not parse file {}: {}", path, error);
+        process::exit(1);
+    })
+}
+
+/// Lädt die Hindernisse und Chunk Settings, spawned die Start-Population und startet die Simulation
+fn start_run(
+    mut load_obstacles_events: EventWriter<LoadObstacles>,
+    mut import_chunk_settings_events: EventWriter<ImportChunkSettings>,
+    mut spawn_cell_events: EventWriter<SpawnCell>,
+    mut run_ticks_events: EventWriter<RunTicks>,
+    headless_run: Res<HeadlessRun>,
+) {
+    if let Some(obstacle_file) = &headless_run.obstacle_file {
+        load_obstacles_events.send(LoadObstacles {
+            path: obstacle_file.clone(),
+        });
+    }
+    if let Some(chunk_settings_image) = &headless_run.chunk_settings_image {
+        import_chunk_settings_events.send(ImportChunkSettings {
+            path: chunk_settings_image.clone(),
+            scale: default(),
+        });
+    }
+    for _ in 0..headless_run.cell_count {
+        spawn_cell_events.send(SpawnCell {
+            energy: headless_run.cell_energy,
+        });
+    }
+    run_ticks_events.send(RunTicks {
+        tick_count: headless_run.tick_count,
+    });
+}
+
+/// Fortschritt beim Beenden des Headless-Laufs
+#[derive(Default, PartialEq)]
+enum FinishState {
+    #[default]
+    Running,
+    Paused,
+    Saved,
+}
+
+/// Wartet bis die gewünschte Anzahl an Ticks erreicht ist (danach pausiert `run_on_tick` die Simulation).
+/// Gespeichert wird erst im nächsten Frame, damit kein Tick-System mehr parallel zum Speichern läuft.
+/// Im Frame danach wird das Programm beendet.
+fn finish_run(
+    mut finish_state: Local<FinishState>,
+    mut save_events: EventWriter<Save>,
+    mut app_exit_events: EventWriter<AppExit>,
+    headless_run: Res<HeadlessRun>,
+    tick_count: Res<TickCount>,
+) {
+    match *finish_state {
+        FinishState::Running => {
+            if **tick_count >= headless_run.tick_count {
+                *finish_state = FinishState::Paused;
+            }
+        }
+        FinishState::Paused => {
+            save_events.send(Save {
+                save_name: headless_run.save_name.clone(),
+            });
+            *finish_state = FinishState::Saved;
+        }
+        FinishState::Saved => {
+            println!(
+                "Saved {} after {} ticks",
+                headless_run.save_name, **tick_count
+            );
+            app_exit_events.send(AppExit);
+        }
+    }
+}
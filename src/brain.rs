@@ -1,13 +1,191 @@
-use bevy::prelude::*;
+use crate::senses::SenseRegistry;
+use bevy::{prelude::*, reflect::ReflectRef};
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    f32::consts::PI,
+};
 
-const BIAS_FUNCTION: fn(f32) -> f32 = |x| (x * 2. - 1.).powi(25) + x * 0.01;
-const WEIGHT_FUNCTION: fn(f32) -> f32 = |x| (x * 2. - 1.).powi(25) + x * 0.01;
-const NEURON_FUNCTION: fn(f32) -> i8 = |x| (2.22 * x - 1.11).powi(15) as i8;
-const CONNECTION_FUNCTION: fn(f32) -> i8 = |x| (2.38 * x - 1.19).powi(7) as i8;
 const ACTIVATION_FUNCTION: fn(f32) -> f32 = |x| x.tanh();
-/// Summe der [Neuron]s die als input verwendet werden und [Neuron]s die als output verwendet werden.
-pub const IMMUNE_NEURON_COUNT: u8 = 8;
+/// Erste Id, die an neue (nicht immune) [Neuron]s vergeben wird.
+/// Die Ids darunter sind für die immunen Input- und Output-[Neuron]s der [SenseRegistry] reserviert.
+pub const FIRST_HIDDEN_NEURON_ID: u32 = 1 << 16;
+/// Anzahl der Input- und Output-[Neuron]s am Anfang jedes [Brain]s aus Saves von vor den Neuron-Ids
+const LEGACY_IMMUNE_NEURON_COUNT: usize = 8;
+/// Platzhalter für die Id eines [Neuron] aus einem Save von vor den Neuron-Ids (siehe `assign_missing_ids`)
+const MISSING_NEURON_ID: u32 = u32::MAX;
+/// Platzhalter für die Innovationsnummer einer Connection aus einem Save von vor der [InnovationRegistry]
+const MISSING_INNOVATION: u64 = u64::MAX;
+
+/// Verteilung, aus der die zufälligen Änderungen von `weight` und `bias` gezogen werden
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PerturbationDistribution {
+    /// Gleichverteilt zwischen `-magnitude` und `magnitude`
+    Uniform,
+    /// Normalverteilt mit `magnitude` als Standardabweichung
+    Normal,
+    /// `magnitude * (2x - 1)^exponent` mit gleichverteiltem x zwischen 0 und 1.\
+    /// Große ungerade Exponenten ergeben meistens sehr kleine und selten große Änderungen.
+    Power(i32),
+}
+
+impl PerturbationDistribution {
+    /// Zieht eine zufällige Änderung mit der angegebenen Stärke
+    pub fn sample(&self, magnitude: f32, rng: &mut impl Rng) -> f32 {
+        match self {
+            Self::Uniform => magnitude * (rng.gen::<f32>() * 2. - 1.),
+            Self::Normal => {
+                // Box-Muller-Transformation (1 - x, damit nie ln(0) berechnet wird)
+                let radius = (-2. * (1. - rng.gen::<f32>()).ln()).sqrt();
+                magnitude * radius * (2. * PI * rng.gen::<f32>()).cos()
+            }
+            Self::Power(exponent) => magnitude * (rng.gen::<f32>() * 2. - 1.).powi(*exponent),
+        }
+    }
+}
+
+/// Einstellungen dafür, wie stark [Brain]s bei der Fortpflanzung mutieren
+/// Wird in einem Save als eigene `mutation_settings.ron` neben der `simulation_settings.ron` gespeichert.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutationSettings {
+    /// Erwartete Anzahl an neuen [Neuron]s pro Mutation.
+    /// Werte über 1 werden akzeptiert (1.5 bedeutet, dass mindestens
+    /// ein [Neuron] hinzugefügt wird und ein 50%-ige Chance auf ein weiteres besteht)
+    pub neuron_add_chance: f32,
+    /// Erwartete Anzahl an entfernten [Neuron]s pro Mutation (wie `neuron_add_chance`)
+    pub neuron_remove_chance: f32,
+    /// Erwartete Anzahl an neuen Connections ([NeuronInput]s) pro Mutation (wie `neuron_add_chance`)
+    pub connection_add_chance: f32,
+    /// Erwartete Anzahl an entfernten Connections pro Mutation (wie `neuron_add_chance`)
+    pub connection_remove_chance: f32,
+    pub weight_distribution: PerturbationDistribution,
+    pub weight_magnitude: f32,
+    pub bias_distribution: PerturbationDistribution,
+    pub bias_magnitude: f32,
+    /// Verteilung der Änderungen der Körpereigenschaften im [Genome](crate::genome::Genome)
+    pub genome_distribution: PerturbationDistribution,
+    pub genome_magnitude: f32,
+    /// Standardabweichung, mit der sich die `mutation_rate` im [Genome](crate::genome::Genome) logarithmisch verändert
+    /// (0 bedeutet, dass sich die Mutationsrate nicht weiterentwickelt)
+    pub mutation_rate_adaptation: f32,
+}
+
+// Die Standartwerte entsprechen ungefähr den früher fest eingebauten Mutations-Funktionen
+impl Default for MutationSettings {
+    fn default() -> Self {
+        Self {
+            neuron_add_chance: 0.1,
+            neuron_remove_chance: 0.1,
+            connection_add_chance: 0.12,
+            connection_remove_chance: 0.12,
+            weight_distribution: PerturbationDistribution::Power(25),
+            weight_magnitude: 1.,
+            bias_distribution: PerturbationDistribution::Power(25),
+            bias_magnitude: 1.,
+            genome_distribution: PerturbationDistribution::Normal,
+            genome_magnitude: 0.02,
+            mutation_rate_adaptation: 0.1,
+        }
+    }
+}
+
+impl MutationSettings {
+    /// Gibt die [MutationSettings] zurück, bei denen alle Wahrscheinlichkeiten und Stärken mit `factor` multipliziert sind
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            neuron_add_chance: self.neuron_add_chance * factor,
+            neuron_remove_chance: self.neuron_remove_chance * factor,
+            connection_add_chance: self.connection_add_chance * factor,
+            connection_remove_chance: self.connection_remove_chance * factor,
+            weight_magnitude: self.weight_magnitude * factor,
+            bias_magnitude: self.bias_magnitude * factor,
+            genome_magnitude: self.genome_magnitude * factor,
+            ..self.clone()
+        }
+    }
+}
+
+/// Wandelt eine erwartete Anzahl in eine zufällige ganze Anzahl um
+/// (1.5 ergibt immer 1 und mit 50%-iger Chance noch 1 dazu)
+fn sample_count(chance: f32, rng: &mut impl Rng) -> u32 {
+    let mut count = chance.max(0.).floor() as u32;
+    if rng.gen::<f32>() < chance.max(0.).fract() {
+        count += 1;
+    }
+    count
+}
+
+/// Vergibt weltweit eindeutige Ids für [Neuron]s und Innovationsnummern für Connections (wie bei NEAT).\
+/// Die gleiche Connection (gleiche Ids von Start- und Ziel-[Neuron]) bekommt in allen [Brain]s die gleiche Innovationsnummer,
+/// dadurch können [Brain]s beim Crossover aneinander ausgerichtet und miteinander verglichen werden.\
+/// Während eines Laufs wächst `connection_innovations` mit jeder neuen Connection,
+/// beim Speichern werden deshalb nur die Innovationsnummern übernommen, die noch ein [Brain] verwendet (siehe `pruned`).
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct InnovationRegistry {
+    next_neuron_id: u32,
+    next_innovation: u64,
+    /// Bereits vergebene Innovationsnummern nach (Id des Start-[Neuron], Id des Ziel-[Neuron])
+    connection_innovations: BTreeMap<(u32, u32), u64>,
+}
+
+impl Default for InnovationRegistry {
+    fn default() -> Self {
+        Self {
+            next_neuron_id: FIRST_HIDDEN_NEURON_ID,
+            next_innovation: 0,
+            connection_innovations: BTreeMap::new(),
+        }
+    }
+}
+
+impl InnovationRegistry {
+    /// Vergibt eine neue, noch nie verwendete Id für ein [Neuron]
+    pub fn new_neuron_id(&mut self) -> u32 {
+        let neuron_id = self.next_neuron_id;
+        self.next_neuron_id += 1;
+        neuron_id
+    }
+
+    /// Gibt die Innovationsnummer der Connection von `from_neuron_id` nach `to_neuron_id` zurück.\
+    /// Wurde diese Connection noch nie erstellt, wird eine neue Innovationsnummer vergeben.
+    pub fn connection_innovation(&mut self, from_neuron_id: u32, to_neuron_id: u32) -> u64 {
+        let next_innovation = &mut self.next_innovation;
+        *self
+            .connection_innovations
+            .entry((from_neuron_id, to_neuron_id))
+            .or_insert_with(|| {
+                let innovation = *next_innovation;
+                *next_innovation += 1;
+                innovation
+            })
+    }
+
+    /// Gibt eine Kopie zurück, die nur noch die Innovationsnummern der Connections von `brains` kennt.\
+    /// `next_innovation` bleibt gleich, damit entfernte Innovationsnummern nie erneut vergeben werden.
+    pub fn pruned<'a>(&self, brains: impl IntoIterator<Item = &'a Brain>) -> Self {
+        let used_innovations: HashSet<u64> = brains
+            .into_iter()
+            .flat_map(|brain| brain.neurons.iter())
+            .flat_map(|neuron| neuron.inputs.iter().map(|input| input.innovation))
+            .collect();
+        Self {
+            connection_innovations: self
+                .connection_innovations
+                .iter()
+                .filter(|(_, innovation)| used_innovations.contains(innovation))
+                .map(|(&connection, &innovation)| (connection, innovation))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Anzahl an bekannten Connections
+    pub fn connection_count(&self) -> usize {
+        self.connection_innovations.len()
+    }
+}
 
 #[derive(Default, Debug, Clone, Component, Reflect)]
 #[reflect(Component)]
@@ -18,11 +196,12 @@ pub struct Brain {
 impl Brain {
     /// # Funktion `new()`
     /// Erzeugt ein neues [Brain].
-    /// Erstellt beim Erzeugen `IMMUNE_NEURON_COUNT` anzahl an [Neuron]s.
-    pub fn new() -> Self {
+    /// Erstellt beim Erzeugen ein [Neuron] für jeden Sensor und Actuator der [SenseRegistry].
+    pub fn new(sense_registry: &SenseRegistry) -> Self {
         let mut neurons = Vec::new();
-        for _ in 0..IMMUNE_NEURON_COUNT {
+        for neuron_id in sense_registry.neuron_ids() {
             neurons.push(Neuron {
+                id: neuron_id,
                 inputs: Vec::new(),
                 bias: 0.,
                 output: 0.,
@@ -31,113 +210,295 @@ impl Brain {
         Self { neurons }
     }
 
+    /// # Funktion AssignMissingIds
+    /// Vergibt Ids und Innovationsnummern an [Neuron]s und Connections aus Saves von vor der [InnovationRegistry].\
+    /// Damals lagen die Input- und Output-[Neuron]s immer am Anfang, ihre Id ist deshalb ihr Index
+    /// (siehe [SenseRegistry]). Die übrigen [Neuron]s bekommen `FIRST_HIDDEN_NEURON_ID` plus ihren Index,
+    /// die Connections eine Innovationsnummer aus der `innovation_registry`.
+    pub fn assign_missing_ids(&mut self, innovation_registry: &mut InnovationRegistry) {
+        for (neuron_index, neuron) in self.neurons.iter_mut().enumerate() {
+            if neuron.id != MISSING_NEURON_ID {
+                continue;
+            }
+            neuron.id = if neuron_index < LEGACY_IMMUNE_NEURON_COUNT {
+                neuron_index as u32
+            } else {
+                FIRST_HIDDEN_NEURON_ID + neuron_index as u32
+            };
+            // Die Id darf später nicht noch einmal vergeben werden
+            innovation_registry.next_neuron_id =
+                innovation_registry.next_neuron_id.max(neuron.id + 1);
+        }
+        let neuron_ids: Vec<u32> = self.neurons.iter().map(|neuron| neuron.id).collect();
+        for neuron in &mut self.neurons {
+            for input in &mut neuron.inputs {
+                if input.innovation == MISSING_INNOVATION {
+                    input.innovation = innovation_registry
+                        .connection_innovation(neuron_ids[input.neuron_index], neuron.id);
+                }
+            }
+        }
+    }
+
+    /// # Funktion AdaptToSenses
+    /// Passt ein (z.B. aus einem alten Save geladenes) [Brain] an die [SenseRegistry] an.\
+    /// Die Input- und Output-[Neuron]s werden über ihre Ids gesucht und an den Anfang sortiert,
+    /// fehlende werden ohne Connections neu erstellt.
+    /// Alle anderen [Neuron]s behalten ihre Reihenfolge und folgen danach, die Connections bleiben erhalten.
+    pub fn adapt_to_senses(&mut self, sense_registry: &SenseRegistry) {
+        if self
+            .neurons
+            .iter()
+            .map(|neuron| neuron.id)
+            .take(sense_registry.neuron_count())
+            .eq(sense_registry.neuron_ids())
+        {
+            return;
+        }
+        let mut old_neurons: Vec<Option<Neuron>> = self.neurons.drain(..).map(Some).collect();
+        let old_indices: HashMap<u32, usize> = old_neurons
+            .iter()
+            .enumerate()
+            .map(|(old_index, neuron)| (neuron.as_ref().unwrap().id, old_index))
+            .collect();
+        // Neuer Index für jeden alten Index
+        let mut new_indices = vec![0; old_neurons.len()];
+        for neuron_id in sense_registry.neuron_ids() {
+            let neuron = match old_indices.get(&neuron_id) {
+                Some(&old_index) => {
+                    new_indices[old_index] = self.neurons.len();
+                    old_neurons[old_index].take().unwrap()
+                }
+                None => Neuron {
+                    id: neuron_id,
+                    inputs: Vec::new(),
+                    bias: 0.,
+                    output: 0.,
+                },
+            };
+            self.neurons.push(neuron);
+        }
+        for (old_index, neuron) in old_neurons.into_iter().enumerate() {
+            if let Some(neuron) = neuron {
+                new_indices[old_index] = self.neurons.len();
+                self.neurons.push(neuron);
+            }
+        }
+        for neuron in &mut self.neurons {
+            for input in &mut neuron.inputs {
+                input.neuron_index = new_indices[input.neuron_index];
+            }
+        }
+    }
+
     /// # Funktion Mutate
-    /// Erstellt/ Löscht eine Zufällig Anzahl an [Neuron]s, die Abhängig von der Anzahl [Neuron]s im Vergleich zur Norm (35)\
+    /// Erstellt/ Löscht eine Zufällig Anzahl an [Neuron]s und Connections, abhängig von den [MutationSettings].\
     /// Beim Erstellen eines [Neuron] wird diesem eine eingehende Verbindung ([NeuronInput]) und eine ausgehende Verbindung zugewiesen.
+    /// Neue [Neuron]s und Connections bekommen ihre Id bzw. Innovationsnummer von der [InnovationRegistry].\
+    /// Alle Zufallswerte werden aus `rng` gezogen, damit Mutationen mit einem festen Seed reproduzierbar sind.
     /// # Panics
     /// Panic kann auftreten, falls das [Brain] keine [Neuron]s enthält.
-    pub fn mutate(&mut self) {
-        let neuron_change = NEURON_FUNCTION(random::<f32>());
-        if neuron_change as i8 >= 0 {
-            // Fügt Neuronen hinzu
-            for _ in 0..neuron_change {
-                let mut new_neuron = Neuron {
-                    inputs: Vec::new(),
-                    bias: 0.,
-                    output: 0.,
-                };
-                new_neuron.inputs.push(NeuronInput {
-                    neuron_index: (random::<f32>() * self.neurons.len() as f32) as usize,
-                    weight: random::<f32>() * 2. - 1.,
-                });
-                let neurons_len = self.neurons.len();
-                self.neurons[(random::<f32>() * neurons_len as f32) as usize]
-                    .inputs
-                    .push(NeuronInput {
-                        neuron_index: neurons_len,
-                        weight: (random::<f32>() * 2. - 1.),
-                    });
-                self.neurons.push(new_neuron);
+    pub fn mutate(
+        &mut self,
+        mutation_settings: &MutationSettings,
+        innovation_registry: &mut InnovationRegistry,
+        rng: &mut impl Rng,
+    ) {
+        // Fügt Neuronen hinzu
+        for _ in 0..sample_count(mutation_settings.neuron_add_chance, rng) {
+            let mut new_neuron = Neuron {
+                id: innovation_registry.new_neuron_id(),
+                inputs: Vec::new(),
+                bias: 0.,
+                output: 0.,
+            };
+            let from_neuron_index = (rng.gen::<f32>() * self.neurons.len() as f32) as usize;
+            new_neuron.inputs.push(NeuronInput {
+                neuron_index: from_neuron_index,
+                innovation: innovation_registry
+                    .connection_innovation(self.neurons[from_neuron_index].id, new_neuron.id),
+                weight: rng.gen::<f32>() * 2. - 1.,
+            });
+            let neurons_len = self.neurons.len();
+            let to_neuron = &mut self.neurons[(rng.gen::<f32>() * neurons_len as f32) as usize];
+            to_neuron.inputs.push(NeuronInput {
+                neuron_index: neurons_len,
+                innovation: innovation_registry.connection_innovation(new_neuron.id, to_neuron.id),
+                weight: (rng.gen::<f32>() * 2. - 1.),
+            });
+            self.neurons.push(new_neuron);
+        }
+        // Entfernt Neuronen
+        for _ in 0..sample_count(mutation_settings.neuron_remove_chance, rng) {
+            let to_delete_neuron_id = (rng.gen::<f32>() * self.neurons.len() as f32) as usize;
+            // Überspringe entfernen, wenn immune neuron betroffen ist
+            if self.neurons[to_delete_neuron_id].id < FIRST_HIDDEN_NEURON_ID {
+                continue;
             }
-        } else {
-            // Entfernt Neuronen
-            for _ in 0..(-neuron_change) {
-                let to_delete_neuron_id = (random::<f32>() * self.neurons.len() as f32) as usize;
-                // Überspringe entfernen, wenn immune neuron betroffen ist
-                if to_delete_neuron_id < IMMUNE_NEURON_COUNT as usize {
-                    continue;
-                }
-                for neuron in &mut self.neurons {
-                    let mut input_index = 0;
-                    while input_index < neuron.inputs.len() {
-                        if neuron.inputs[input_index].neuron_index == to_delete_neuron_id {
-                            neuron.inputs.remove(input_index);
-                        } else {
-                            input_index += 1;
-                        }
+            for neuron in &mut self.neurons {
+                let mut input_index = 0;
+                while input_index < neuron.inputs.len() {
+                    if neuron.inputs[input_index].neuron_index == to_delete_neuron_id {
+                        neuron.inputs.remove(input_index);
+                    } else {
+                        input_index += 1;
                     }
-                    for input in &mut neuron.inputs {
-                        if input.neuron_index > to_delete_neuron_id {
-                            input.neuron_index -= 1;
-                        }
+                }
+                for input in &mut neuron.inputs {
+                    if input.neuron_index > to_delete_neuron_id {
+                        input.neuron_index -= 1;
                     }
                 }
-                self.neurons.remove(to_delete_neuron_id);
             }
+            self.neurons.remove(to_delete_neuron_id);
         }
-        let connection_change = CONNECTION_FUNCTION(random::<f32>());
-        if connection_change >= 0 {
-            // Fügt Connections hinzu
-            for _ in 0..connection_change {
-                let new_connection_from_neuron_id =
-                    (random::<f32>() * self.neurons.len() as f32) as usize;
-                let new_connection_to_neuron_id =
-                    (random::<f32>() * self.neurons.len() as f32) as usize;
-                self.neurons[new_connection_to_neuron_id]
-                    .inputs
-                    .push(NeuronInput {
-                        neuron_index: new_connection_from_neuron_id,
-                        weight: (random::<f32>() * 2. - 1.),
-                    });
-            }
-        } else {
-            // Entfernt Connections
-            for _ in 0..(-connection_change) {
-                let connection_count = self
-                    .neurons
-                    .iter()
-                    .fold(0, |count, neuron| count + neuron.inputs.len());
-                let to_delete_connection = (random::<f32>() * connection_count as f32) as usize;
-                let mut counted_connections = 0;
-                for neuron in &mut self.neurons {
-                    let new_counted_connections = counted_connections + neuron.inputs.len();
-                    if new_counted_connections > to_delete_connection {
-                        let neuron_input_index = to_delete_connection - counted_connections;
-                        neuron.inputs.remove(neuron_input_index);
-                        break;
-                    }
-                    counted_connections = new_counted_connections;
+        // Fügt Connections hinzu
+        for _ in 0..sample_count(mutation_settings.connection_add_chance, rng) {
+            let new_connection_from_neuron_id =
+                (rng.gen::<f32>() * self.neurons.len() as f32) as usize;
+            let new_connection_to_neuron_id =
+                (rng.gen::<f32>() * self.neurons.len() as f32) as usize;
+            let innovation = innovation_registry.connection_innovation(
+                self.neurons[new_connection_from_neuron_id].id,
+                self.neurons[new_connection_to_neuron_id].id,
+            );
+            self.neurons[new_connection_to_neuron_id]
+                .inputs
+                .push(NeuronInput {
+                    neuron_index: new_connection_from_neuron_id,
+                    innovation,
+                    weight: (rng.gen::<f32>() * 2. - 1.),
+                });
+        }
+        // Entfernt Connections
+        for _ in 0..sample_count(mutation_settings.connection_remove_chance, rng) {
+            let connection_count = self
+                .neurons
+                .iter()
+                .fold(0, |count, neuron| count + neuron.inputs.len());
+            let to_delete_connection = (rng.gen::<f32>() * connection_count as f32) as usize;
+            let mut counted_connections = 0;
+            for neuron in &mut self.neurons {
+                let new_counted_connections = counted_connections + neuron.inputs.len();
+                if new_counted_connections > to_delete_connection {
+                    let neuron_input_index = to_delete_connection - counted_connections;
+                    neuron.inputs.remove(neuron_input_index);
+                    break;
                 }
+                counted_connections = new_counted_connections;
             }
         }
         // Verändert weight und bias von Neuronen zufällig
         for neuron in &mut self.neurons {
-            neuron.bias += BIAS_FUNCTION(random::<f32>());
+            neuron.bias += mutation_settings
+                .bias_distribution
+                .sample(mutation_settings.bias_magnitude, rng);
             for neuron_input in &mut neuron.inputs {
-                neuron_input.weight += WEIGHT_FUNCTION(random::<f32>());
+                neuron_input.weight += mutation_settings
+                    .weight_distribution
+                    .sample(mutation_settings.weight_magnitude, rng);
             }
         }
     }
 
+    /// # Funktion Crossover
+    /// Erzeugt ein Kind-[Brain] aus zwei Eltern.\
+    /// Das Kind übernimmt den Aufbau ([Neuron]s und Connections) von `self`, daher sollte `self` der erfolgreichere Elternteil sein.
+    /// Die Eltern werden über die Ids der [Neuron]s und die Innovationsnummern der Connections aneinander ausgerichtet (wie bei NEAT).
+    /// Für jedes [Neuron] und jede Connection, die es in beiden Eltern gibt, wird zufällig entschieden,
+    /// von welchem Elternteil `bias` bzw. `weight` übernommen wird.
+    pub fn crossover(&self, other: &Brain, rng: &mut impl Rng) -> Brain {
+        let mut child = self.clone();
+        let other_neurons: HashMap<u32, &Neuron> = other
+            .neurons
+            .iter()
+            .map(|neuron| (neuron.id, neuron))
+            .collect();
+        for child_neuron in &mut child.neurons {
+            let Some(other_neuron) = other_neurons.get(&child_neuron.id) else {
+                continue;
+            };
+            if rng.gen::<bool>() {
+                child_neuron.bias = other_neuron.bias;
+            }
+            for child_input in &mut child_neuron.inputs {
+                let other_input = other_neuron
+                    .inputs
+                    .iter()
+                    .find(|other_input| other_input.innovation == child_input.innovation);
+                if let Some(other_input) = other_input {
+                    if rng.gen::<bool>() {
+                        child_input.weight = other_input.weight;
+                    }
+                }
+            }
+        }
+        child
+    }
+
+    /// # Funktion CompatibilityDistance
+    /// Berechnet, wie unterschiedlich zwei [Brain]s sind (wie bei NEAT):\
+    /// `disjoint_coefficient * D / N + weight_coefficient * W`\
+    /// D ist die Anzahl an [Neuron]s (nach Id) und Connections (nach Innovationsnummer), die es nur in einem der beiden [Brain]s gibt,
+    /// N die Anzahl an [Neuron]s und Connections des größeren [Brain]
+    /// und W die durchschnittliche Differenz der `weight`s von Connections, die es in beiden [Brain]s gibt.
+    pub fn compatibility_distance(
+        &self,
+        other: &Brain,
+        disjoint_coefficient: f32,
+        weight_coefficient: f32,
+    ) -> f32 {
+        let neuron_ids = |brain: &Brain| -> HashSet<u32> {
+            brain.neurons.iter().map(|neuron| neuron.id).collect()
+        };
+        let connection_weights = |brain: &Brain| -> HashMap<u64, f32> {
+            brain
+                .neurons
+                .iter()
+                .flat_map(|neuron| &neuron.inputs)
+                .map(|input| (input.innovation, input.weight))
+                .collect()
+        };
+        let (neuron_ids_self, neuron_ids_other) = (neuron_ids(self), neuron_ids(other));
+        let (connection_weights_self, connection_weights_other) =
+            (connection_weights(self), connection_weights(other));
+
+        let mut disjoint_count = neuron_ids_self
+            .symmetric_difference(&neuron_ids_other)
+            .count();
+        let mut matching_count = 0;
+        let mut weight_difference_sum = 0.;
+        for (innovation, weight) in &connection_weights_self {
+            match connection_weights_other.get(innovation) {
+                Some(other_weight) => {
+                    matching_count += 1;
+                    weight_difference_sum += (weight - other_weight).abs();
+                }
+                None => disjoint_count += 1,
+            }
+        }
+        disjoint_count += connection_weights_other
+            .keys()
+            .filter(|innovation| !connection_weights_self.contains_key(innovation))
+            .count();
+
+        let gene_count = (neuron_ids_self.len() + connection_weights_self.len())
+            .max(neuron_ids_other.len() + connection_weights_other.len())
+            .max(1);
+        let average_weight_difference = if matching_count > 0 {
+            weight_difference_sum / matching_count as f32
+        } else {
+            0.
+        };
+        disjoint_coefficient * disjoint_count as f32 / gene_count as f32
+            + weight_coefficient * average_weight_difference
+    }
+
     /// # Funktion WriteNeuron
     /// Gibt den [Output] für ein bestimmtes [Neuron] zurück\
     /// (Falls es existiert, sonst [None])
     pub fn read_neuron(&self, neuron_index: usize) -> Option<f32> {
-        if let Some(neuron) = self.neurons.get(neuron_index) {
-            Some(neuron.output)
-        } else {
-            None
-        }
+        self.neurons.get(neuron_index).map(|neuron| neuron.output)
     }
 
     /// # Funktion WriteNeuron
@@ -179,15 +540,53 @@ impl Brain {
     }
 }
 
-#[derive(Debug, Clone, Reflect, FromReflect)]
+#[derive(Debug, Clone, Reflect)]
 pub struct Neuron {
+    /// Stabile Id des [Neuron] (ändert sich nicht, wenn andere [Neuron]s entfernt werden)
+    pub id: u32,
     pub inputs: Vec<NeuronInput>,
     pub bias: f32,
     pub output: f32,
 }
 
-#[derive(Debug, Clone, Reflect, FromReflect)]
+/// Von Hand implementiert, da ältere Saves noch keine `id` haben
+/// (die abgeleitete Implementierung gibt dann [None] zurück und das Laden des [Brain] schlägt fehl)
+impl FromReflect for Neuron {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let ReflectRef::Struct(neuron) = reflect.reflect_ref() else {
+            return None;
+        };
+        Some(Self {
+            id: neuron
+                .field("id")
+                .map_or(Some(MISSING_NEURON_ID), u32::from_reflect)?,
+            inputs: Vec::from_reflect(neuron.field("inputs")?)?,
+            bias: f32::from_reflect(neuron.field("bias")?)?,
+            output: f32::from_reflect(neuron.field("output")?)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
 pub struct NeuronInput {
     pub neuron_index: usize,
+    /// Innovationsnummer der Connection (siehe [InnovationRegistry])
+    pub innovation: u64,
     pub weight: f32,
 }
+
+/// Von Hand implementiert, da ältere Saves noch keine `innovation` haben
+impl FromReflect for NeuronInput {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let ReflectRef::Struct(neuron_input) = reflect.reflect_ref() else {
+            return None;
+        };
+        Some(Self {
+            neuron_index: usize::from_reflect(neuron_input.field("neuron_index")?)?,
+            innovation: neuron_input
+                .field("innovation")
+                .map_or(Some(MISSING_INNOVATION), u64::from_reflect)?,
+            weight: f32::from_reflect(neuron_input.field("weight")?)?,
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    brain::{MutationSettings, PerturbationDistribution},
+    sim::SimulationSettings,
+};
+use bevy::prelude::*;
+use rand::prelude::*;
+
+/// Kleinster Wert, den eine Körpereigenschaft durch Mutation erreichen kann
+pub const TRAIT_MIN: f32 = 0.2;
+/// Größter Wert, den eine Körpereigenschaft durch Mutation erreichen kann
+const TRAIT_MAX: f32 = 5.;
+/// Kleinste und größte `mutation_rate`
+const MUTATION_RATE_MIN: f32 = 0.01;
+const MUTATION_RATE_MAX: f32 = 10.;
+
+/// Körpereigenschaften einer Zelle, die wie das [Brain](crate::brain::Brain) vererbt werden und mutieren.\
+/// Alle Eigenschaften sind Faktoren auf die globalen Werte in den [SimulationSettings] (1 entspricht den globalen Werten).
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct Genome {
+    /// Faktor auf `cell_radius`.
+    /// Größere Zellen sammeln Nahrung aus größerer Entfernung, verbrauchen aber mit ihrer Fläche mehr Energie.
+    pub size: f32,
+    /// Faktor auf `rotation_speed_max` und `acceleration_max` (kostet `speed_energy_drain * (speed² - 1)` pro Tick,
+    /// langsamere Zellen als 1 sparen also Energie)
+    pub speed: f32,
+    /// Faktor auf die Energie aus Nahrung und auf `base_energy_drain`
+    pub metabolism: f32,
+    /// Faktor auf alle Wahrscheinlichkeiten und Stärken der [MutationSettings] für die Kinder dieser Zelle.
+    /// Mutiert selbst mit (selbstadaptive Mutation), dadurch können Abstammungslinien vorsichtiger oder experimentierfreudiger werden.
+    pub mutation_rate: f32,
+}
+
+impl Default for Genome {
+    fn default() -> Self {
+        Self {
+            size: 1.,
+            speed: 1.,
+            metabolism: 1.,
+            mutation_rate: 1.,
+        }
+    }
+}
+
+impl Genome {
+    /// # Funktion Mutate
+    /// Verändert zuerst die `mutation_rate` (log-normalverteilt mit `mutation_rate_adaptation`)
+    /// und danach alle Körpereigenschaften (mit `genome_distribution` und `genome_magnitude * mutation_rate`).\
+    /// Das [Brain](crate::brain::Brain) des Kindes sollte danach mit `mutation_settings.scaled(mutation_rate)` mutiert werden.
+    pub fn mutate(&mut self, mutation_settings: &MutationSettings, rng: &mut impl Rng) {
+        self.mutation_rate = (self.mutation_rate
+            * PerturbationDistribution::Normal
+                .sample(mutation_settings.mutation_rate_adaptation, rng)
+                .exp())
+        .clamp(MUTATION_RATE_MIN, MUTATION_RATE_MAX);
+        for value in [&mut self.size, &mut self.speed, &mut self.metabolism] {
+            *value = (*value
+                + mutation_settings
+                    .genome_distribution
+                    .sample(mutation_settings.genome_magnitude * self.mutation_rate, rng))
+            .clamp(TRAIT_MIN, TRAIT_MAX);
+        }
+    }
+
+    /// # Funktion Crossover
+    /// Erzeugt ein Kind-[Genome], das jede Eigenschaft zufällig von einem der beiden Eltern übernimmt.
+    pub fn crossover(&self, other: &Genome, rng: &mut impl Rng) -> Genome {
+        let mut pick = |value_self: f32, value_other: f32| {
+            if rng.gen::<bool>() {
+                value_other
+            } else {
+                value_self
+            }
+        };
+        Genome {
+            size: pick(self.size, other.size),
+            speed: pick(self.speed, other.speed),
+            metabolism: pick(self.metabolism, other.metabolism),
+            mutation_rate: pick(self.mutation_rate, other.mutation_rate),
+        }
+    }
+
+    /// Radius der Zelle
+    pub fn radius(&self, simulation_settings: &SimulationSettings) -> f32 {
+        simulation_settings.cell_radius * self.size
+    }
+
+    /// Masse der Zelle für Kollisionen (proportional zur Fläche)
+    pub fn mass(&self) -> f32 {
+        self.size * self.size
+    }
+
+    /// Energieverbrauch pro Tick durch die Körpereigenschaften
+    /// (ein Standard-[Genome] verbraucht genau `base_energy_drain`).\
+    /// Nie negativ, auch wenn eine langsame Zelle mehr spart als sie sonst verbraucht.
+    pub fn energy_drain(&self, simulation_settings: &SimulationSettings) -> f32 {
+        (simulation_settings.base_energy_drain * self.size * self.size * self.metabolism
+            + simulation_settings.speed_energy_drain * (self.speed * self.speed - 1.))
+            .max(0.)
+    }
+}
@@ -0,0 +1,154 @@
+// Bevy Systeme haben oft viele Parameter mit komplexen Query-Typen
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use bevy::prelude::*;
+use brain::{Brain, InnovationRegistry, MutationSettings, Neuron, NeuronInput};
+use genome::Genome;
+use senses::{SenseRegistry, SIGNAL_CHANNEL_COUNT_MAX};
+use sim::{
+    AncestryLog, ApplyChunkSettings, ApplyMutationSettings, ApplySimulationSettings, Cell,
+    CellStats, ChildCooldown, Chunk, ChunkRegistry, ChunkSettings, Clear, CreateWorld, Energy,
+    EnvironmentSchedule, ExportChunkSettings, Food, FoodSpawnFactor, Foodlist, ImportChunkSettings,
+    Lineage, LoadEnvironmentSchedule, LoadObstacles, Obstacle, PaintChunkSettings, Pheromone,
+    Position, RemainingEnergies, Rotation, RunTicks, Save, Signals, SimulationRng,
+    SimulationSettings, SpawnCell, SpawnObstacle, Species, SpeciesRegistry, Step, TickCount,
+    TogglePause, Velocity,
+};
+use ui::{
+    BrainSizeStatistic, CellCountStatistic, ChildCountStatistic, ControlCenterUi, IsOpen, Label,
+    MutationRateStatistic, SpeciesStatistic, Statistic, StatisticData, StatisticLine,
+};
+
+pub mod brain;
+pub mod genome;
+pub mod senses;
+pub mod sim;
+mod tests;
+pub mod ui;
+
+/// Registriert alles, was die Simulation unabhängig von einem Fenster braucht:
+/// Events, Komponenten, Ressourcen, Setup und die Simulation Systeme.
+/// Wird sowohl von `die_ursuppe` als auch von `die_ursuppe-headless` verwendet.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Ui events
+            .add_event::<SpawnCell>()
+            .add_event::<SpawnObstacle>()
+            .add_event::<LoadObstacles>()
+            .add_event::<Clear>()
+            .add_event::<ApplySimulationSettings>()
+            .add_event::<ApplyMutationSettings>()
+            .add_event::<TogglePause>()
+            .add_event::<Step>()
+            .add_event::<RunTicks>()
+            .add_event::<ApplyChunkSettings>()
+            .add_event::<PaintChunkSettings>()
+            .add_event::<ImportChunkSettings>()
+            .add_event::<ExportChunkSettings>()
+            .add_event::<LoadEnvironmentSchedule>()
+            .add_event::<CreateWorld>()
+            .add_event::<Save>()
+            // Register components
+            .register_type::<[f32; 3]>()
+            .register_type::<Vec<f32>>()
+            .register_type::<Vec<u32>>()
+            .register_type::<Option<u64>>()
+            .register_type::<Vec<Vec<Entity>>>()
+            .register_type::<ChunkRegistry>()
+            .register_type::<Foodlist>()
+            .register_type::<ChunkSettings>()
+            .register_type::<Pheromone>()
+            .register_type::<FoodSpawnFactor>()
+            .register_type::<Position>()
+            .register_type::<Obstacle>()
+            .register_type::<Rotation>()
+            .register_type::<Velocity>()
+            .register_type::<Energy>()
+            .register_type::<ChildCooldown>()
+            .register_type::<CellStats>()
+            .register_type::<Species>()
+            .register_type::<Lineage>()
+            .register_type::<Signals>()
+            .register_type::<[f32; SIGNAL_CHANNEL_COUNT_MAX as usize]>()
+            .register_type::<Brain>()
+            .register_type::<Genome>()
+            .register_type::<Vec<Neuron>>()
+            .register_type::<Neuron>()
+            .register_type::<Vec<NeuronInput>>()
+            .register_type::<NeuronInput>()
+            .register_type::<Label>()
+            .register_type::<IsOpen>()
+            .register_type::<StatisticData>()
+            .register_type::<Vec<StatisticLine>>()
+            .register_type::<StatisticLine>()
+            .register_type::<Cell>()
+            .register_type::<Food>()
+            .register_type::<Chunk>()
+            .register_type::<Statistic>()
+            .register_type::<ChildCountStatistic>()
+            .register_type::<CellCountStatistic>()
+            .register_type::<BrainSizeStatistic>()
+            .register_type::<MutationRateStatistic>()
+            .register_type::<SpeciesStatistic>()
+            // Init ressources
+            .init_resource::<SimulationSettings>()
+            .init_resource::<MutationSettings>()
+            .init_resource::<InnovationRegistry>()
+            .init_resource::<SenseRegistry>()
+            .init_resource::<SpeciesRegistry>()
+            .init_resource::<AncestryLog>()
+            .init_resource::<EnvironmentSchedule>()
+            .init_resource::<SimulationRng>()
+            .init_resource::<TickCount>()
+            .init_resource::<RemainingEnergies>()
+            .init_resource::<ControlCenterUi>()
+            // Setup
+            .add_startup_system(sim::setup_chunks)
+            .add_startup_system(ui::setup_statistics)
+            // Ui Event-Handler
+            .add_system(sim::spawn_cells.before(sim::spawn_food))
+            .add_system(sim::spawn_obstacles.before(sim::spawn_food))
+            .add_system(sim::load_obstacles.before(sim::spawn_food))
+            .add_system(sim::apply_chunk_settings)
+            .add_system(sim::paint_chunk_settings.after(sim::apply_chunk_settings))
+            .add_system(
+                sim::import_chunk_settings
+                    .after(sim::apply_chunk_settings)
+                    .before(sim::spawn_food),
+            )
+            .add_system(sim::export_chunk_settings.after(sim::import_chunk_settings))
+            .add_system(sim::load_environment_schedule.before(sim::apply_environment_schedule))
+            // Läuft vor allen anderen Systemen, die Commands werden direkt danach angewendet
+            .add_system(sim::create_world.at_start())
+            .add_system(sim::apply_simulation_settings)
+            .add_system(sim::apply_mutation_settings)
+            .add_system(sim::toggle_pause)
+            .add_system(sim::clear)
+            .add_system(sim::save)
+            .add_system(
+                sim::update_sense_registry
+                    .after(sim::apply_simulation_settings)
+                    .before(sim::adapt_brains),
+            )
+            .add_system(sim::adapt_brains.before(sim::tick_cells))
+            .add_system(sim::insert_missing_components.before(sim::tick_cells))
+            // Simulation Systeme, die an Tick beteiligt sind
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(sim::run_on_tick)
+                    .with_system(sim::apply_environment_schedule)
+                    .with_system(sim::spawn_food.after(sim::apply_environment_schedule))
+                    .with_system(sim::tick_cells.after(sim::spawn_food))
+                    .with_system(sim::collide_cells.after(sim::tick_cells))
+                    .with_system(sim::update_pheromones.after(sim::tick_cells))
+                    .with_system(sim::collide_obstacles.after(sim::collide_cells))
+                    .with_system(sim::despawn_food.after(sim::collide_obstacles))
+                    .with_system(sim::despawn_cells.after(sim::despawn_food))
+                    .with_system(sim::update_species.after(sim::despawn_cells))
+                    .with_system(sim::write_mutation_rate_statistic.after(sim::tick_cells)),
+            );
+    }
+}
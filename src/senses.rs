@@ -0,0 +1,258 @@
+use crate::{
+    brain::{Brain, FIRST_HIDDEN_NEURON_ID},
+    sim::{SimulationSettings, VisionHit},
+};
+use bevy::prelude::*;
+
+/// Maximale Anzahl an Sehstrahlen pro Zelle (jeder Sehstrahl hat zwei [Sensor]en)
+pub const VISION_RAY_COUNT_MAX: u8 = 8;
+/// Maximale Anzahl an Signal-Kanälen (jeder verwendete Kanal hat einen [Sensor] und einen [Actuator])
+pub const SIGNAL_CHANNEL_COUNT_MAX: u8 = 4;
+
+/// Alles, was eine Zelle in einem Tick wahrnimmt (wird in `tick_cells` berechnet
+/// und von den [Sensor]en in die Input-Neuronen geschrieben)
+#[derive(Debug, Clone)]
+pub struct CellPerception {
+    /// Winkel zur nächsten Nahrung relativ zur Blickrichtung (zwischen -PI und PI)
+    pub nearest_food_angle: f32,
+    pub nearest_food_distance_squared: f32,
+    pub age: u32,
+    pub energy: f32,
+    /// Ob die Zelle eine andere Zelle berührt
+    pub touching_cell: bool,
+    /// Pro Signal-Kanal die nach Distanz gewichtete Summe der Signale aller Nachbarn
+    pub received_signals: [f32; SIGNAL_CHANNEL_COUNT_MAX as usize],
+    /// [Pheromone](crate::sim::Pheromone) im Chunk der Zelle
+    pub pheromone: f32,
+    /// Helligkeit des Tag-Nacht-Zyklus zwischen 0 (Mitternacht) und 1 (Mittag)
+    pub light_level: f32,
+    /// Gefälle des [Pheromone](crate::sim::Pheromone) relativ zur Blickrichtung (vorwärts, links)
+    pub pheromone_gradient: (f32, f32),
+    /// Pro Sehstrahl die Distanz (relativ zur `vision_range`) und die Art des getroffenen Objekts
+    pub vision: [(f32, VisionHit); VISION_RAY_COUNT_MAX as usize],
+}
+
+/// Alles, was eine Zelle in einem Tick tun will (wird von den [Actuator]en aus den Output-Neuronen gelesen)
+#[derive(Debug, Clone, Default)]
+pub struct CellActions {
+    pub rotation: f32,
+    pub acceleration: f32,
+    pub want_child: f32,
+    /// Stärke, mit der die Zelle eine berührte Zelle angreift (nur positive Werte greifen an)
+    pub attack: f32,
+    /// Werte, die die Zelle auf den Signal-Kanälen sendet
+    pub signals: [f32; SIGNAL_CHANNEL_COUNT_MAX as usize],
+    /// Menge an [Pheromone](crate::sim::Pheromone), die die Zelle abgibt (nur positive Werte, höchstens 1)
+    pub pheromone_deposit: f32,
+}
+
+/// Ein Input eines [Brain]: schreibt einen Wert aus der [CellPerception] in sein Input-Neuron
+pub struct Sensor {
+    pub name: String,
+    /// Stabile Id des Input-Neuron (kleiner als `FIRST_HIDDEN_NEURON_ID`)
+    pub neuron_id: u32,
+    pub writer: Box<dyn Fn(&CellPerception) -> f32 + Send + Sync>,
+}
+
+/// Ein Output eines [Brain]: liest den Wert seines Output-Neuron in die [CellActions]
+pub struct Actuator {
+    pub name: String,
+    /// Stabile Id des Output-Neuron (kleiner als `FIRST_HIDDEN_NEURON_ID`)
+    pub neuron_id: u32,
+    pub reader: Box<dyn Fn(&mut CellActions, f32) + Send + Sync>,
+}
+
+/// Legt fest, welche Inputs ([Sensor]en) und Outputs ([Actuator]en) ein [Brain] hat.\
+/// Im [Brain] liegen zuerst die Input-Neuronen in der Reihenfolge der [Sensor]en,
+/// danach die Output-Neuronen in der Reihenfolge der [Actuator]en und danach alle anderen [Neuron](crate::brain::Neuron)s.
+/// Die Neuronen werden über ihre stabilen Ids zugeordnet,
+/// dadurch können neue [Sensor]en hinzugefügt werden, ohne alte [Brain]s unbrauchbar zu machen (siehe `Brain::adapt_to_senses`).
+#[derive(Resource)]
+pub struct SenseRegistry {
+    sensors: Vec<Sensor>,
+    actuators: Vec<Actuator>,
+    /// Anzahl an Signal-Kanälen, für die [Sensor]en und [Actuator]en registriert sind
+    signal_channel_count: u32,
+}
+
+impl Default for SenseRegistry {
+    fn default() -> Self {
+        Self::with_signal_channels(SimulationSettings::default().signal_channel_count)
+    }
+}
+
+impl SenseRegistry {
+    /// Erzeugt die [SenseRegistry] mit allen Standard-[Sensor]en und -[Actuator]en
+    /// und `signal_channel_count` Signal-Kanälen (höchstens `SIGNAL_CHANNEL_COUNT_MAX`)
+    pub fn with_signal_channels(signal_channel_count: u32) -> Self {
+        let mut sense_registry = Self::new();
+        sense_registry.register_sensor("Nearest food angle", 0, |perception| {
+            perception.nearest_food_angle
+        });
+        sense_registry.register_sensor("Nearest food distance", 1, |perception| {
+            perception.nearest_food_distance_squared
+        });
+        sense_registry.register_sensor("Age", 2, |perception| perception.age as f32);
+        sense_registry.register_sensor("Energy", 3, |perception| perception.energy);
+        sense_registry.register_sensor("Oscillator", 4, |perception| {
+            (perception.age as f32 * 0.1).sin()
+        });
+        sense_registry.register_sensor("Light", 38, |perception| perception.light_level);
+        for ray_index in 0..VISION_RAY_COUNT_MAX as usize {
+            let neuron_id = 8 + 2 * ray_index as u32;
+            sense_registry.register_sensor(
+                format!("Ray {ray_index} distance"),
+                neuron_id,
+                move |perception| perception.vision[ray_index].0,
+            );
+            sense_registry.register_sensor(
+                format!("Ray {ray_index} kind"),
+                neuron_id + 1,
+                move |perception| perception.vision[ray_index].1.neuron_value(),
+            );
+        }
+        sense_registry.register_sensor("Touching cell", 24, |perception| {
+            perception.touching_cell as u8 as f32
+        });
+        // Die Ids 5 bis 7 entsprechen den Output-Neuronen der Brains von vor der SenseRegistry
+        sense_registry.register_actuator("Rotation", 5, |actions, value| actions.rotation = value);
+        sense_registry.register_actuator("Acceleration", 6, |actions, value| {
+            actions.acceleration = value
+        });
+        sense_registry
+            .register_actuator("Child wish", 7, |actions, value| actions.want_child = value);
+        sense_registry.register_actuator("Attack", 25, |actions, value| actions.attack = value);
+        sense_registry.signal_channel_count =
+            signal_channel_count.min(SIGNAL_CHANNEL_COUNT_MAX as u32);
+        for channel in 0..sense_registry.signal_channel_count as usize {
+            sense_registry.register_sensor(
+                format!("Signal {channel} received"),
+                26 + channel as u32,
+                move |perception| perception.received_signals[channel],
+            );
+            sense_registry.register_actuator(
+                format!("Signal {channel}"),
+                26 + SIGNAL_CHANNEL_COUNT_MAX as u32 + channel as u32,
+                move |actions, value| actions.signals[channel] = value,
+            );
+        }
+        sense_registry.register_sensor("Pheromone", 34, |perception| perception.pheromone);
+        sense_registry.register_sensor("Pheromone gradient forward", 35, |perception| {
+            perception.pheromone_gradient.0
+        });
+        sense_registry.register_sensor("Pheromone gradient left", 36, |perception| {
+            perception.pheromone_gradient.1
+        });
+        sense_registry.register_actuator("Pheromone deposit", 37, |actions, value| {
+            actions.pheromone_deposit = value
+        });
+        sense_registry
+    }
+
+    /// Erzeugt eine leere [SenseRegistry] ohne [Sensor]en und [Actuator]en
+    pub fn new() -> Self {
+        Self {
+            sensors: Vec::new(),
+            actuators: Vec::new(),
+            signal_channel_count: 0,
+        }
+    }
+
+    /// Fügt einen [Sensor] hinzu (wird im [Brain] an die Input-Neuronen angehängt)
+    /// # Panics
+    /// Falls `neuron_id` bereits vergeben ist oder nicht kleiner als `FIRST_HIDDEN_NEURON_ID` ist.
+    pub fn register_sensor(
+        &mut self,
+        name: impl Into<String>,
+        neuron_id: u32,
+        writer: impl Fn(&CellPerception) -> f32 + Send + Sync + 'static,
+    ) {
+        self.assert_neuron_id_free(neuron_id);
+        self.sensors.push(Sensor {
+            name: name.into(),
+            neuron_id,
+            writer: Box::new(writer),
+        });
+    }
+
+    /// Fügt einen [Actuator] hinzu (wird im [Brain] an die Output-Neuronen angehängt)
+    /// # Panics
+    /// Falls `neuron_id` bereits vergeben ist oder nicht kleiner als `FIRST_HIDDEN_NEURON_ID` ist.
+    pub fn register_actuator(
+        &mut self,
+        name: impl Into<String>,
+        neuron_id: u32,
+        reader: impl Fn(&mut CellActions, f32) + Send + Sync + 'static,
+    ) {
+        self.assert_neuron_id_free(neuron_id);
+        self.actuators.push(Actuator {
+            name: name.into(),
+            neuron_id,
+            reader: Box::new(reader),
+        });
+    }
+
+    fn assert_neuron_id_free(&self, neuron_id: u32) {
+        assert!(neuron_id < FIRST_HIDDEN_NEURON_ID);
+        assert!(!self.neuron_ids().any(|used_id| used_id == neuron_id));
+    }
+
+    /// Getter für Feld `sensors`.
+    pub fn sensors(&self) -> &Vec<Sensor> {
+        &self.sensors
+    }
+
+    /// Getter für Feld `actuators`.
+    pub fn actuators(&self) -> &Vec<Actuator> {
+        &self.actuators
+    }
+
+    /// Getter für Feld `signal_channel_count`.
+    pub fn signal_channel_count(&self) -> u32 {
+        self.signal_channel_count
+    }
+
+    /// Index des Input-Neuron von Sensor `sensor_index` im [Brain]
+    pub fn sensor_neuron_index(&self, sensor_index: usize) -> usize {
+        sensor_index
+    }
+
+    /// Index des Output-Neuron von Actuator `actuator_index` im [Brain]
+    pub fn actuator_neuron_index(&self, actuator_index: usize) -> usize {
+        self.sensors.len() + actuator_index
+    }
+
+    /// Anzahl an Input- und Output-Neuronen
+    pub fn neuron_count(&self) -> usize {
+        self.sensors.len() + self.actuators.len()
+    }
+
+    /// Ids der Input- und Output-Neuronen in der Reihenfolge, in der sie im [Brain] liegen
+    pub fn neuron_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.sensors
+            .iter()
+            .map(|sensor| sensor.neuron_id)
+            .chain(self.actuators.iter().map(|actuator| actuator.neuron_id))
+    }
+
+    /// Schreibt die Werte aller [Sensor]en in die Input-Neuronen von `brain`
+    pub fn write_sensors(&self, brain: &mut Brain, perception: &CellPerception) {
+        for (sensor_index, sensor) in self.sensors.iter().enumerate() {
+            brain.write_neuron(
+                self.sensor_neuron_index(sensor_index),
+                (sensor.writer)(perception),
+            );
+        }
+    }
+
+    /// Liest die Werte aller Output-Neuronen von `brain` mit den [Actuator]en aus
+    pub fn read_actuators(&self, brain: &Brain) -> CellActions {
+        let mut actions = CellActions::default();
+        for (actuator_index, actuator) in self.actuators.iter().enumerate() {
+            if let Some(value) = brain.read_neuron(self.actuator_neuron_index(actuator_index)) {
+                (actuator.reader)(&mut actions, value);
+            }
+        }
+        actions
+    }
+}
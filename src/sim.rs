@@ -1,8 +1,10 @@
 use crate::{
-    brain::Brain,
+    brain::{Brain, InnovationRegistry, MutationSettings, FIRST_HIDDEN_NEURON_ID},
+    genome::Genome,
+    senses::{CellPerception, SenseRegistry, SIGNAL_CHANNEL_COUNT_MAX, VISION_RAY_COUNT_MAX},
     ui::{
-        BrainSizeStatistic, CellCountStatistic, ChildCountStatistic, ControlCenterUi, Statistic,
-        StatisticData,
+        BrainSizeStatistic, CellCountStatistic, ChildCountStatistic, ControlCenterUi, Label,
+        MutationRateStatistic, SpeciesStatistic, Statistic, StatisticData, StatisticLine,
     },
 };
 use bevy::{
@@ -16,26 +18,109 @@ use bevy::{
     time::Stopwatch,
 };
 use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
-use std::{f32::consts::PI, fs, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    f32::consts::PI,
+    fmt::Write,
+    fs,
+    path::Path,
+    time::Instant,
+};
 
 /// Einstellungen für den Verlauf der Simulation
 #[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationSettings {
-    /// Radius einer Zelle
+    /// Seed für den Zufallsgenerator der Simulation ([SimulationRng])
+    pub seed: u64,
+    /// Radius einer Zelle mit `size` 1 aus dem [Genome]
     pub cell_radius: f32,
     /// Radius von Nahrung
     pub food_radius: f32,
     pub base_energy_drain: f32,
+    /// Energieverbrauch pro verstecktem [Neuron](crate::brain::Neuron)
+    /// (die Input- und Output-Neuronen der [SenseRegistry] kosten nichts)
     pub neuron_energy_drain: f32,
     pub connection_energy_drain: f32,
     pub age_energy_drain: f32,
     pub energy_required_for_split: f32,
     pub rotation_speed_max: f32,
     pub acceleration_max: f32,
+    /// Faktor für den Energieverbrauch durch `speed` aus dem [Genome]: pro Tick kommt `speed_energy_drain * (speed² - 1)` dazu
+    /// (bei `speed` 1 nichts, langsamere Zellen sparen Energie, der gesamte Verbrauch bleibt aber mindestens 0)
+    pub speed_energy_drain: f32,
     /// Die angestrebte Dauer in Sekunden zwischen Ticks
     pub tick_delta_seconds: f32,
+    /// Wie viele Ticks maximal pro Frame durchgeführt werden, sobald ein Tick aussteht
+    pub ticks_per_frame: u32,
+    /// Wie viele Sekunden pro Frame maximal für Ticks verwendet werden,
+    /// bevor der Frame gezeichnet wird (der erste Tick eines Frames wird immer durchgeführt)
+    pub frame_time_budget_seconds: f32,
     pub child_cooldown: u32,
+    /// Ob sich Zellen nur paarweise fortpflanzen (Kind-Brain ist ein Crossover beider Eltern)
+    pub sexual_reproduction: bool,
+    /// Maximale Distanz zwischen zwei Zellen, die sich miteinander fortpflanzen
+    pub mating_radius: f32,
+    /// Maximale Distanz, in der eine Zelle Nahrung wahrnimmt (am Tag, siehe `night_sensing_factor`)
+    pub sensing_radius: f32,
+    /// Map Breite in Chunks (wird beim Start und mit [CreateWorld] übernommen)
+    pub map_width: u32,
+    /// Map Höhe in Chunks (wird beim Start und mit [CreateWorld] übernommen)
+    pub map_height: u32,
+    /// Größe der Chunks (wird beim Start und mit [CreateWorld] übernommen)
+    pub chunk_size: f32,
+    /// Verhalten am Rand der Map
+    pub world_topology: WorldTopology,
+    /// Ob Zellen miteinander kollidieren (sonst bewegen sie sich durcheinander hindurch)
+    pub cell_collisions: bool,
+    /// Anteil der Geschwindigkeit entlang der Stoßrichtung, der bei einer Kollision erhalten bleibt
+    /// (1 ist ein elastischer Stoß, 0 ein vollständig unelastischer Stoß)
+    pub cell_collision_elasticity: f32,
+    /// Energie, die ein Angriff mit voller Stärke dem Ziel pro Tick abzieht
+    pub attack_drain: f32,
+    /// Anteil der abgezogenen Energie, den der Angreifer bekommt
+    pub attack_efficiency: f32,
+    /// Energie, die ein Angriff den Angreifer pro Tick kostet (auch ohne Ziel)
+    pub attack_cost: f32,
+    /// Ob tote Zellen als Nahrung (Kadaver) liegen bleiben
+    pub leave_corpses: bool,
+    /// Energie, die der Kadaver einer toten Zelle mindestens hat. Stirbt die Zelle am Rand der Map
+    /// oder durch einen Angriff, enthält der Kadaver stattdessen ihre restliche Energie (siehe [RemainingEnergies]), falls diese größer ist.
+    pub corpse_body_energy: f32,
+    /// Anzahl an Sehstrahlen pro Zelle (höchstens `VISION_RAY_COUNT_MAX`)
+    pub vision_ray_count: u32,
+    /// Winkel in Radiant zwischen dem ersten und dem letzten Sehstrahl
+    pub vision_angle_spread: f32,
+    /// Reichweite der Sehstrahlen (am Tag, siehe `night_sensing_factor`)
+    pub vision_range: f32,
+    /// Anzahl an Signal-Kanälen, auf denen Zellen senden und empfangen (höchstens `SIGNAL_CHANNEL_COUNT_MAX`).
+    /// Nur für diese Kanäle bekommen die [Brain]s Input- und Output-Neuronen (siehe `update_sense_registry`).
+    pub signal_channel_count: u32,
+    /// Maximale Distanz, in der Signale empfangen werden
+    pub signal_range: f32,
+    /// Menge an [Pheromone], die eine Zelle pro Tick höchstens in ihren Chunk abgibt
+    pub pheromone_deposit_rate: f32,
+    /// Anteil zwischen 0 und 1, mit dem sich das [Pheromone] pro Tick an die Nachbar-Chunks angleicht
+    pub pheromone_diffusion_rate: f32,
+    /// Anteil zwischen 0 und 1, um den das [Pheromone] pro Tick zerfällt
+    pub pheromone_decay_rate: f32,
+    /// Länge eines Tag-Nacht-Zyklus in Ticks (0 deaktiviert den Zyklus, dann ist es immer hell)
+    pub day_length: u32,
+    /// Faktor für die `spawn_chance` bei völliger Dunkelheit (am Mittag ist der Faktor 1)
+    pub night_food_spawn_factor: f32,
+    /// Faktor für `sensing_radius` und `vision_range` bei völliger Dunkelheit (am Mittag ist der Faktor 1).
+    /// Weiter entfernte Objekte werden nicht wahrgenommen, wahrgenommene Distanzen bleiben gleich.
+    pub night_sensing_factor: f32,
+    /// Alle wie viele Ticks die Zellen neu in [Species] eingeteilt werden (0 deaktiviert die Einteilung)
+    pub speciation_interval: u32,
+    /// Maximale `compatibility_distance` zwischen einer Zelle und dem Repräsentanten ihrer [Species]
+    pub species_compatibility_threshold: f32,
+    /// Gewichtung von Neuronen und Connections, die es nur in einem der beiden [Brain]s gibt
+    pub species_disjoint_coefficient: f32,
+    /// Gewichtung der durchschnittlichen `weight`-Differenz übereinstimmender Connections
+    pub species_weight_coefficient: f32,
     /// Ob die Simulation pausiert ist
     pub is_paused: bool,
 }
@@ -44,6 +129,7 @@ pub struct SimulationSettings {
 impl Default for SimulationSettings {
     fn default() -> Self {
         Self {
+            seed: 0,
             cell_radius: 5.,
             food_radius: 3.,
             base_energy_drain: 0.4,
@@ -53,23 +139,392 @@ impl Default for SimulationSettings {
             energy_required_for_split: 10.,
             rotation_speed_max: 1.,
             acceleration_max: 1.7,
+            speed_energy_drain: 0.05,
             tick_delta_seconds: 0.02,
+            ticks_per_frame: 1,
+            frame_time_budget_seconds: 0.03,
             child_cooldown: 10,
+            sexual_reproduction: false,
+            mating_radius: 15.,
+            sensing_radius: 50.,
+            map_width: 40,
+            map_height: 40,
+            chunk_size: 50.,
+            world_topology: WorldTopology::default(),
+            cell_collisions: true,
+            cell_collision_elasticity: 0.5,
+            attack_drain: 2.,
+            attack_efficiency: 0.5,
+            attack_cost: 0.05,
+            leave_corpses: false,
+            corpse_body_energy: 1.,
+            vision_ray_count: 3,
+            vision_angle_spread: PI / 2.,
+            vision_range: 100.,
+            signal_channel_count: 2,
+            signal_range: 50.,
+            pheromone_deposit_rate: 1.,
+            pheromone_diffusion_rate: 0.1,
+            pheromone_decay_rate: 0.01,
+            day_length: 0,
+            night_food_spawn_factor: 0.5,
+            night_sensing_factor: 0.5,
+            speciation_interval: 50,
+            species_compatibility_threshold: 0.6,
+            species_disjoint_coefficient: 1.,
+            species_weight_coefficient: 0.4,
             is_paused: true,
         }
     }
 }
 
+impl SimulationSettings {
+    /// Helligkeit zwischen 0 (Mitternacht) und 1 (Mittag) im Tick `tick`
+    pub fn light_level(&self, tick: u64) -> f32 {
+        if self.day_length == 0 {
+            return 1.;
+        }
+        let day_progress = (tick % self.day_length as u64) as f32 / self.day_length as f32;
+        0.5 + 0.5 * (2. * PI * day_progress).sin()
+    }
+
+    /// Faktor zwischen `night_factor` (bei Helligkeit 0) und 1 (bei Helligkeit 1)
+    pub fn daylight_factor(light_level: f32, night_factor: f32) -> f32 {
+        night_factor + (1. - night_factor) * light_level
+    }
+}
+
+/// Verhalten am Rand der Map
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorldTopology {
+    /// Zellen, die die Map verlassen, sterben
+    #[default]
+    BoundedLethal,
+    /// Zellen prallen vom Rand der Map ab
+    BoundedReflective,
+    /// Zellen, die die Map verlassen, kommen auf der gegenüberliegenden Seite wieder herein.
+    /// Nachbarschaften und Distanzen gehen über den Rand hinweg.
+    Torus,
+}
+
+/// Zeitlicher Verlauf der Umwelt: Effekte, die abhängig vom Tick und der Position
+/// die `spawn_chance` der Chunks verändern (über den [FoodSpawnFactor], die [ChunkSettings] bleiben unverändert).\
+/// Wird als RON-Datei geladen, z.B.:
+/// ```ron
+/// (effects: [
+///     Season(period: 2000, amplitude: 0.8),
+///     Hotspot(start: (x: 500., y: 1000.), velocity: (x: 0.5, y: 0.), radius: 400., strength: 4.),
+///     Drought(start_tick: 10000, duration: 1000, interval: 20000, factor: 0.1),
+/// ])
+/// ```
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentSchedule {
+    pub effects: Vec<EnvironmentEffect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EnvironmentEffect {
+    /// Jahreszeiten: Faktor `1 + amplitude * sin(2π * (tick / period + phase))` (nicht unter 0)
+    Season {
+        /// Länge eines Jahres in Ticks
+        period: u64,
+        amplitude: f32,
+        /// Verschiebung als Anteil eines Jahres
+        #[serde(default)]
+        phase: f32,
+    },
+    /// Nahrungs-Hotspot, der mit `velocity` pro Tick über die Map wandert
+    /// (und am Rand auf der gegenüberliegenden Seite wieder erscheint).
+    /// In der Mitte ist der Faktor `1 + strength`, zum `radius` hin fällt er linear auf 1 ab.
+    Hotspot {
+        /// Mitte bei Tick 0
+        start: Position,
+        velocity: Position,
+        radius: f32,
+        strength: f32,
+    },
+    /// Dürre: Ab `start_tick` wird für `duration` Ticks mit `factor` multipliziert,
+    /// danach wiederholt sich die Dürre alle `interval` Ticks (0: nur einmal)
+    Drought {
+        start_tick: u64,
+        duration: u64,
+        #[serde(default)]
+        interval: u64,
+        factor: f32,
+    },
+}
+
+impl EnvironmentSchedule {
+    /// Produkt der Faktoren aller Effekte an `position` im Tick `tick`
+    pub fn food_spawn_factor(
+        &self,
+        position: &Position,
+        tick: u64,
+        chunk_registry: &ChunkRegistry,
+    ) -> f32 {
+        let mut food_spawn_factor = 1.;
+        for effect in &self.effects {
+            food_spawn_factor *= match *effect {
+                EnvironmentEffect::Season {
+                    period,
+                    amplitude,
+                    phase,
+                } => {
+                    if period == 0 {
+                        continue;
+                    }
+                    let year_progress = (tick % period) as f32 / period as f32 + phase;
+                    (1. + amplitude * (2. * PI * year_progress).sin()).max(0.)
+                }
+                EnvironmentEffect::Hotspot {
+                    start,
+                    velocity,
+                    radius,
+                    strength,
+                } => {
+                    let mut center = Position {
+                        x: start.x + velocity.x * tick as f32,
+                        y: start.y + velocity.y * tick as f32,
+                    };
+                    chunk_registry.keep_on_map(&mut center, WorldTopology::Torus);
+                    let relative_position =
+                        chunk_registry.relative_position(&center, position, WorldTopology::Torus);
+                    let distance = (relative_position.x * relative_position.x
+                        + relative_position.y * relative_position.y)
+                        .sqrt();
+                    1. + strength * (1. - distance / radius).max(0.)
+                }
+                EnvironmentEffect::Drought {
+                    start_tick,
+                    duration,
+                    interval,
+                    factor,
+                } => {
+                    let is_active = tick >= start_tick
+                        && if interval == 0 {
+                            tick - start_tick < duration
+                        } else {
+                            (tick - start_tick) % interval < duration
+                        };
+                    if is_active {
+                        factor
+                    } else {
+                        1.
+                    }
+                }
+            };
+        }
+        food_spawn_factor
+    }
+}
+
+/// Zufallsgenerator der Simulation.
+/// Alle Zufallswerte, die den Verlauf der Simulation beeinflussen, werden hieraus gezogen,
+/// damit gleiche Seeds und gleiche Einstellungen zu gleichen Ergebnissen führen.
+/// Der Zustand wird mitgespeichert, damit ein geladener Lauf dort weitermacht, wo er gespeichert wurde.
+#[derive(Resource, Deref, DerefMut, Serialize, Deserialize)]
+pub struct SimulationRng(pub ChaCha12Rng);
+
+impl Default for SimulationRng {
+    fn default() -> Self {
+        Self(ChaCha12Rng::seed_from_u64(
+            SimulationSettings::default().seed,
+        ))
+    }
+}
+
+/// Anzahl der bisher durchgeführten Ticks
+#[derive(Default, Resource, Deref, DerefMut, Serialize, Deserialize)]
+pub struct TickCount(pub u64);
+
+/// Energie, die Zellen in diesem Tick beim Sterben noch hatten
+/// (am Rand bei [WorldTopology::BoundedLethal] oder der Teil eines tödlichen Angriffs, den der Angreifer nicht bekommt).\
+/// Wird von `tick_cells` gefüllt und von `despawn_cells` in die Kadaver übernommen.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct RemainingEnergies(pub HashMap<Entity, f32>);
+
 #[derive(Default, Component, Serialize, Deserialize, Reflect)]
 #[reflect(Component, MapEntities)]
 pub struct ChunkRegistry {
     /// Größe der Chunks
     pub chunk_size: f32,
-    /// Map Größe in Chunks
+    /// Map Breite in Chunks (x-Richtung, heißt aus Kompatibilität mit alten Saves noch `map_size`)
     pub map_size: u32,
+    /// Map Höhe in Chunks (y-Richtung, bei alten Saves 0 bis `insert_missing_components` sie setzt)
+    pub map_height: u32,
     pub entries: Vec<Vec<Entity>>,
 }
 
+impl ChunkRegistry {
+    /// Erzeugt eine Map mit `map_width` x `map_height` Chunks der Größe `chunk_size`.
+    /// `chunk_bundle` gibt zu den Indizes eines Chunks das [ChunkBundle] zurück (die [Position] wird danach gesetzt).
+    pub fn spawn(
+        commands: &mut Commands,
+        chunk_size: f32,
+        map_width: u32,
+        map_height: u32,
+        mut chunk_bundle: impl FnMut(u32, u32) -> ChunkBundle,
+    ) -> ChunkRegistry {
+        let mut chunk_registry = ChunkRegistry {
+            chunk_size,
+            map_size: map_width,
+            map_height,
+            entries: Vec::new(),
+        };
+
+        // Neue Chunks erzeugen und Referenz im Chunk-Registry speichern
+        for index in 0..map_width {
+            chunk_registry.entries.push(Vec::new());
+            for idy in 0..map_height {
+                let chunk_entity = commands
+                    .spawn(ChunkBundle {
+                        position: Position {
+                            x: (index as f32 * chunk_size + chunk_size / 2.),
+                            y: (idy as f32 * chunk_size + chunk_size / 2.),
+                        },
+                        ..chunk_bundle(index, idy)
+                    })
+                    .id();
+                chunk_registry.entries[index as usize].push(chunk_entity);
+            }
+        }
+        chunk_registry
+    }
+
+    /// Breite und Höhe der Map
+    pub fn map_length(&self) -> (f32, f32) {
+        (
+            self.map_size as f32 * self.chunk_size,
+            self.map_height as f32 * self.chunk_size,
+        )
+    }
+
+    /// Gibt den Chunk zurück, in dem `position` liegt ([None] außerhalb der Map)
+    pub fn chunk_at(&self, position: &Position) -> Option<Entity> {
+        self.chunk_index_at(position)
+            .map(|(index, idy)| self.entries[index][idy])
+    }
+
+    /// Gibt die Indizes des Chunks zurück, in dem `position` liegt ([None] außerhalb der Map)
+    pub fn chunk_index_at(&self, position: &Position) -> Option<(usize, usize)> {
+        let (map_width, map_height) = self.map_length();
+        if position.x < 0. || position.y < 0. || position.x >= map_width || position.y >= map_height
+        {
+            return None;
+        }
+        Some((
+            (position.x / self.chunk_size) as usize,
+            (position.y / self.chunk_size) as usize,
+        ))
+    }
+
+    /// Vektor von `from` nach `to` (bei [WorldTopology::Torus] der kürzeste Weg, auch über den Rand der Map)
+    pub fn relative_position(
+        &self,
+        from: &Position,
+        to: &Position,
+        world_topology: WorldTopology,
+    ) -> Position {
+        let mut relative_position = Position {
+            x: to.x - from.x,
+            y: to.y - from.y,
+        };
+        if world_topology == WorldTopology::Torus {
+            let (map_width, map_height) = self.map_length();
+            relative_position.x -= (relative_position.x / map_width).round() * map_width;
+            relative_position.y -= (relative_position.y / map_height).round() * map_height;
+        }
+        relative_position
+    }
+
+    /// Bringt `position` zurück auf die Map
+    /// (bei [WorldTopology::Torus] auf die gegenüberliegende Seite, sonst an den Rand)
+    pub fn keep_on_map(&self, position: &mut Position, world_topology: WorldTopology) {
+        let (map_width, map_height) = self.map_length();
+        for (value, map_length) in [(&mut position.x, map_width), (&mut position.y, map_height)] {
+            *value = if world_topology == WorldTopology::Torus {
+                // `rem_euclid` kann bei sehr kleinen negativen Werten genau `map_length` ergeben
+                let wrapped_value = value.rem_euclid(map_length);
+                if wrapped_value >= map_length {
+                    0.
+                } else {
+                    wrapped_value
+                }
+            } else {
+                value.clamp(0., map_length - 0.01)
+            };
+        }
+    }
+
+    /// Distanz von `origin` in Richtung `direction` (normiert) bis zum Rand der Map
+    pub fn ray_wall_distance(&self, origin: &Position, direction: (f32, f32)) -> f32 {
+        let (map_width, map_height) = self.map_length();
+        let axis_distance = |origin: f32, direction: f32, map_length: f32| {
+            if direction > 0. {
+                (map_length - origin) / direction
+            } else if direction < 0. {
+                -origin / direction
+            } else {
+                f32::INFINITY
+            }
+        };
+        axis_distance(origin.x, direction.0, map_width)
+            .min(axis_distance(origin.y, direction.1, map_height))
+            .max(0.)
+    }
+
+    /// Gibt alle Chunks zurück, die sich mit dem Kreis mit `radius` um `position` überschneiden
+    /// (über so viele Chunks wie nötig, Chunks außerhalb der Map werden ignoriert,
+    /// bei [WorldTopology::Torus] stattdessen auf der gegenüberliegenden Seite gesucht)
+    pub fn chunks_in_radius(
+        &self,
+        position: &Position,
+        radius: f32,
+        world_topology: WorldTopology,
+    ) -> Vec<Entity> {
+        let (map_width, map_height) = (self.map_size as i32, self.map_height as i32);
+        let chunk_index_range = |center: f32, map_size: i32| {
+            let min = ((center - radius) / self.chunk_size).floor() as i32;
+            let max = ((center + radius) / self.chunk_size).floor() as i32;
+            if world_topology == WorldTopology::Torus {
+                if max - min + 1 >= map_size {
+                    // Jeder Chunk wird höchstens einmal zurückgegeben (jeweils auf der näheren Seite)
+                    let min = (center / self.chunk_size).floor() as i32 - (map_size - 1) / 2;
+                    min..=min + map_size - 1
+                } else {
+                    min..=max
+                }
+            } else {
+                min.max(0)..=max.min(map_size - 1)
+            }
+        };
+        let mut chunk_entities = Vec::new();
+        for chunk_index in chunk_index_range(position.x, map_width) {
+            for chunk_idy in chunk_index_range(position.y, map_height) {
+                // Abstand zum nächsten Punkt im Chunk prüfen, damit Ecken außerhalb des Kreises übersprungen werden
+                let nearest_x = position.x.clamp(
+                    chunk_index as f32 * self.chunk_size,
+                    (chunk_index + 1) as f32 * self.chunk_size,
+                );
+                let nearest_y = position.y.clamp(
+                    chunk_idy as f32 * self.chunk_size,
+                    (chunk_idy + 1) as f32 * self.chunk_size,
+                );
+                let distance_squared = (nearest_x - position.x) * (nearest_x - position.x)
+                    + (nearest_y - position.y) * (nearest_y - position.y);
+                if distance_squared <= radius * radius {
+                    chunk_entities.push(
+                        self.entries[chunk_index.rem_euclid(map_width) as usize]
+                            [chunk_idy.rem_euclid(map_height) as usize],
+                    );
+                }
+            }
+        }
+        chunk_entities
+    }
+}
+
 impl MapEntities for ChunkRegistry {
     fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
         for index_x in 0..self.entries.len() {
@@ -100,7 +555,7 @@ impl MapEntities for Foodlist {
     }
 }
 
-#[derive(Debug, Component, Reflect)]
+#[derive(Debug, Clone, Component, Reflect)]
 #[reflect(Component)]
 pub struct ChunkSettings {
     /// Die Wahrscheinlichkeit, dass in diesem Chunk Essen spawnt.
@@ -124,7 +579,170 @@ impl Default for ChunkSettings {
     }
 }
 
-#[derive(Default, Clone, Copy, Debug, Component, Reflect)]
+/// Konzentration des Pheromon-Felds in einem Chunk.
+/// Zellen geben Pheromon ab und nehmen den Wert und das Gefälle an ihrer Position wahr,
+/// in `update_pheromones` verteilt es sich auf die Nachbar-Chunks und zerfällt.
+#[derive(Default, Debug, Clone, Copy, Component, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct Pheromone(pub f32);
+
+/// Faktor, mit dem die `spawn_chance` eines Chunks im aktuellen Tick multipliziert wird.
+/// Wird von `apply_environment_schedule` aus dem [EnvironmentSchedule] und dem Tag-Nacht-Zyklus berechnet.
+#[derive(Debug, Clone, Copy, Component, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct FoodSpawnFactor(pub f32);
+
+impl Default for FoodSpawnFactor {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Statisches Hindernis, das Zellen blockiert und von Sehstrahlen als Wand gesehen wird.
+/// Hindernisse werden über das Control Center gesetzt oder mit `LoadObstacles` aus einer RON-Datei
+/// (Liste von [Obstacle]s) geladen und mit der Scene gespeichert.
+#[derive(Debug, Clone, Copy, Component, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub enum Obstacle {
+    /// Massiver Kreis (Nahrung spawnt nicht darin)
+    Circle { center: Position, radius: f32 },
+    /// Dünne Wand von `start` bis `end`
+    Segment { start: Position, end: Position },
+}
+
+impl Default for Obstacle {
+    fn default() -> Self {
+        Self::Circle {
+            center: Position::default(),
+            radius: 0.,
+        }
+    }
+}
+
+impl Obstacle {
+    /// Gibt `position` so verschoben zurück, dass sie auf der Seite des Hindernisses liegt
+    /// (bei [WorldTopology::Torus] kann das über den Rand der Map hinweg sein, sonst bleibt sie gleich)
+    fn nearest_image(
+        &self,
+        position: &Position,
+        chunk_registry: &ChunkRegistry,
+        world_topology: WorldTopology,
+    ) -> Position {
+        let reference = match self {
+            Obstacle::Circle { center, .. } => *center,
+            Obstacle::Segment { start, end } => Position {
+                x: (start.x + end.x) / 2.,
+                y: (start.y + end.y) / 2.,
+            },
+        };
+        let relative_position =
+            chunk_registry.relative_position(&reference, position, world_topology);
+        Position {
+            x: reference.x + relative_position.x,
+            y: reference.y + relative_position.y,
+        }
+    }
+
+    /// Berechnet, ob sich der Kreis mit `radius` um `position` mit dem Hindernis überschneidet.\
+    /// Gibt die Richtung (normiert), in die der Kreis aus dem Hindernis geschoben werden muss,
+    /// und die Tiefe der Überschneidung zurück.
+    pub fn contact(
+        &self,
+        position: &Position,
+        radius: f32,
+        chunk_registry: &ChunkRegistry,
+        world_topology: WorldTopology,
+    ) -> Option<((f32, f32), f32)> {
+        let position = &self.nearest_image(position, chunk_registry, world_topology);
+        let (closest_point, outer_radius) = match self {
+            Obstacle::Circle { center, radius } => (*center, *radius),
+            Obstacle::Segment { start, end } => {
+                // Punkt der Wand, der `position` am nächsten ist
+                let segment = (end.x - start.x, end.y - start.y);
+                let segment_length_squared = segment.0 * segment.0 + segment.1 * segment.1;
+                let factor = if segment_length_squared > 0. {
+                    (((position.x - start.x) * segment.0 + (position.y - start.y) * segment.1)
+                        / segment_length_squared)
+                        .clamp(0., 1.)
+                } else {
+                    0.
+                };
+                (
+                    Position {
+                        x: start.x + segment.0 * factor,
+                        y: start.y + segment.1 * factor,
+                    },
+                    0.,
+                )
+            }
+        };
+        let relative_position = (position.x - closest_point.x, position.y - closest_point.y);
+        let distance = (relative_position.0 * relative_position.0
+            + relative_position.1 * relative_position.1)
+            .sqrt();
+        let depth = outer_radius + radius - distance;
+        if depth <= 0. {
+            return None;
+        }
+        // Liegt der Kreis genau auf dem Hindernis, wird er nach rechts geschoben
+        let normal = if distance > 0. {
+            (
+                relative_position.0 / distance,
+                relative_position.1 / distance,
+            )
+        } else {
+            (1., 0.)
+        };
+        Some((normal, depth))
+    }
+
+    /// Distanz von `origin` in Richtung `direction` (normiert) bis zum Hindernis ([None], falls es nicht getroffen wird)
+    pub fn ray_distance(
+        &self,
+        origin: &Position,
+        direction: (f32, f32),
+        chunk_registry: &ChunkRegistry,
+        world_topology: WorldTopology,
+    ) -> Option<f32> {
+        let origin = &self.nearest_image(origin, chunk_registry, world_topology);
+        match self {
+            Obstacle::Circle { center, radius } => {
+                ray_circle_distance(origin, direction, center, *radius)
+            }
+            Obstacle::Segment { start, end } => ray_segment_distance(origin, direction, start, end),
+        }
+    }
+
+    /// Strecke, die sich ein Kreis mit `radius` von `origin` in Richtung `direction` (normiert) höchstens bewegen darf,
+    /// damit er nicht durch das Hindernis hindurch springt ([None], falls er es auf dem Weg nicht trifft).\
+    /// Vor einer Wand bleibt der Mittelpunkt knapp auf seiner Seite, an einem Kreis knapp in der Überschneidung,
+    /// damit `collide_obstacles` die Bewegung in das Hindernis hinein stoppt.
+    /// Überschneidet sich der Kreis schon mit einem massiven Kreis, wird nichts begrenzt.
+    pub fn sweep_distance(
+        &self,
+        origin: &Position,
+        direction: (f32, f32),
+        radius: f32,
+        chunk_registry: &ChunkRegistry,
+        world_topology: WorldTopology,
+    ) -> Option<f32> {
+        let origin = &self.nearest_image(origin, chunk_registry, world_topology);
+        match self {
+            Obstacle::Circle {
+                center,
+                radius: obstacle_radius,
+            } => {
+                let distance =
+                    ray_circle_distance(origin, direction, center, obstacle_radius + radius)?;
+                (distance > 0.).then_some(distance + 0.01)
+            }
+            Obstacle::Segment { start, end } => ray_segment_distance(origin, direction, start, end)
+                .map(|distance| (distance - 0.01).max(0.)),
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, Component, Reflect, FromReflect, Serialize, Deserialize)]
 #[reflect(Component)]
 pub struct Position {
     pub x: f32,
@@ -135,7 +753,7 @@ pub struct Position {
 #[reflect(Component)]
 pub struct Rotation(pub f32);
 
-#[derive(Default, Debug, Component, Reflect)]
+#[derive(Default, Clone, Copy, Debug, Component, Reflect)]
 #[reflect(Component)]
 pub struct Velocity {
     pub x: f32,
@@ -146,6 +764,11 @@ pub struct Velocity {
 #[reflect(Component)]
 pub struct Energy(pub f32);
 
+/// Werte, die eine Zelle im letzten Tick auf den Signal-Kanälen gesendet hat
+#[derive(Default, Debug, Clone, Copy, Component, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct Signals(pub [f32; SIGNAL_CHANNEL_COUNT_MAX as usize]);
+
 #[derive(Default, Debug, Component, Deref, DerefMut, Reflect)]
 #[reflect(Component)]
 pub struct ChildCooldown(pub u32);
@@ -157,6 +780,183 @@ pub struct CellStats {
     pub child_count: u32,
 }
 
+/// Abstammung einer Zelle (Details stehen im [AncestryLog])
+#[derive(Default, Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct Lineage {
+    /// Eindeutige Id der Zelle (0 bedeutet, dass die Zelle nicht im [AncestryLog] steht)
+    pub id: u64,
+    /// Id des Elternteils (bei sexueller Fortpflanzung der Elternteil, der den Aufbau vorgibt)
+    pub parent_id: Option<u64>,
+    /// Anzahl an Vorfahren bis zu einer manuell gespawnten Zelle
+    pub generation: u32,
+}
+
+/// Eintrag einer Zelle im [AncestryLog]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AncestryEntry {
+    pub id: u64,
+    pub parent_id: Option<u64>,
+    /// Id des zweiten Elternteils (nur bei sexueller Fortpflanzung)
+    pub mate_id: Option<u64>,
+    pub generation: u32,
+    pub birth_tick: u64,
+    /// [None], solange die Zelle noch lebt
+    pub death_tick: Option<u64>,
+}
+
+/// Alle Zellen, die es in der Simulation je gab.
+/// Die Id einer Zelle ist ihr Index in `entries` + 1.
+#[derive(Default, Resource, Serialize, Deserialize)]
+pub struct AncestryLog {
+    pub entries: Vec<AncestryEntry>,
+}
+
+impl AncestryLog {
+    /// Trägt eine neue Zelle ein und gibt ihre [Lineage] zurück.
+    /// Ohne `parent` ist die Zelle der Anfang einer neuen Abstammungslinie.
+    pub fn record_birth(
+        &mut self,
+        parent: Option<&Lineage>,
+        mate: Option<&Lineage>,
+        birth_tick: u64,
+    ) -> Lineage {
+        // Eltern, die nicht im Log stehen (Id 0), werden ignoriert
+        let parent = parent.filter(|parent| parent.id != 0);
+        let lineage = Lineage {
+            id: self.entries.len() as u64 + 1,
+            parent_id: parent.map(|parent| parent.id),
+            generation: parent.map_or(0, |parent| parent.generation + 1),
+        };
+        self.entries.push(AncestryEntry {
+            id: lineage.id,
+            parent_id: lineage.parent_id,
+            mate_id: mate.map(|mate| mate.id).filter(|mate_id| *mate_id != 0),
+            generation: lineage.generation,
+            birth_tick,
+            death_tick: None,
+        });
+        lineage
+    }
+
+    /// Setzt den Todes-Tick einer Zelle
+    pub fn record_death(&mut self, lineage: &Lineage, death_tick: u64) {
+        if let Some(entry) = self.entry_mut(lineage.id) {
+            entry.death_tick = Some(death_tick);
+        }
+    }
+
+    fn entry_mut(&mut self, id: u64) -> Option<&mut AncestryEntry> {
+        id.checked_sub(1)
+            .and_then(|index| self.entries.get_mut(index as usize))
+    }
+
+    /// Exportiert den Stammbaum im Newick-Format.\
+    /// Alle Abstammungslinien hängen an einer gemeinsamen (namenlosen) Wurzel,
+    /// die Länge einer Kante ist die Anzahl an Ticks zwischen der Geburt des Elternteils und der des Kindes.
+    pub fn to_newick(&self) -> String {
+        // Kinder zu jedem Eintrag sammeln
+        let mut children = vec![Vec::new(); self.entries.len()];
+        let mut roots = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            match entry.parent_id {
+                Some(parent_id) => children[parent_id as usize - 1].push(index),
+                None => roots.push(index),
+            }
+        }
+
+        // Baum ohne Rekursion durchlaufen, da Abstammungslinien sehr lang werden können
+        enum Step {
+            Open(usize),
+            Close(usize),
+            Separator,
+        }
+        let push_nodes = |stack: &mut Vec<Step>, nodes: &[usize]| {
+            for (position, node) in nodes.iter().enumerate().rev() {
+                stack.push(Step::Open(*node));
+                if position > 0 {
+                    stack.push(Step::Separator);
+                }
+            }
+        };
+        let label = |index: usize| {
+            let entry = &self.entries[index];
+            let parent_birth_tick = entry.parent_id.map_or(0, |parent_id| {
+                self.entries[parent_id as usize - 1].birth_tick
+            });
+            format!("{}:{}", entry.id, entry.birth_tick - parent_birth_tick)
+        };
+        let mut newick = "(".to_string();
+        let mut stack = Vec::new();
+        push_nodes(&mut stack, &roots);
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Open(index) if children[index].is_empty() => newick.push_str(&label(index)),
+                Step::Open(index) => {
+                    newick.push('(');
+                    stack.push(Step::Close(index));
+                    push_nodes(&mut stack, &children[index]);
+                }
+                Step::Close(index) => {
+                    newick.push(')');
+                    newick.push_str(&label(index));
+                }
+                Step::Separator => newick.push(','),
+            }
+        }
+        newick.push_str(");");
+        newick
+    }
+
+    /// Exportiert alle Einträge als JSON-Liste
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.entries).unwrap()
+    }
+}
+
+/// Species, der eine Zelle bei der letzten Einteilung (siehe `update_species`) zugeordnet wurde.
+/// Kinder übernehmen die Species ihres Elternteils bis zur nächsten Einteilung.
+#[derive(Default, Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct Species {
+    /// Stabile Id der Species (0 bedeutet, dass die Zelle noch keiner Species zugeordnet wurde)
+    pub id: u32,
+    pub color: [f32; 3],
+}
+
+impl Species {
+    /// Erzeugt eine Species mit einer aus der Id berechneten Farbe.
+    /// Der Farbton wird um den goldenen Schnitt weitergedreht, damit aufeinanderfolgende Ids gut unterscheidbar sind.
+    pub fn new(id: u32) -> Self {
+        let color = Color::hsl((id as f32 * 0.618_034).fract() * 360., 0.8, 0.6);
+        Self {
+            id,
+            color: [color.r(), color.g(), color.b()],
+        }
+    }
+}
+
+/// Vergibt die Ids für neue [Species]
+#[derive(Resource, Serialize, Deserialize)]
+pub struct SpeciesRegistry {
+    next_species_id: u32,
+}
+
+impl Default for SpeciesRegistry {
+    fn default() -> Self {
+        Self { next_species_id: 1 }
+    }
+}
+
+impl SpeciesRegistry {
+    /// Erzeugt eine neue [Species] mit einer noch nie verwendeten Id
+    pub fn new_species(&mut self) -> Species {
+        let species = Species::new(self.next_species_id);
+        self.next_species_id += 1;
+        species
+    }
+}
+
 #[derive(Default, Component, Reflect)]
 #[reflect(Component)]
 pub struct Cell;
@@ -173,12 +973,16 @@ pub struct Chunk;
 pub struct CellBundle {
     pub cell: Cell,
     pub brain: Brain,
+    pub genome: Genome,
+    pub signals: Signals,
     pub position: Position,
     pub rotation: Rotation,
     pub velocity: Velocity,
     pub energy: Energy,
     pub child_cooldown: ChildCooldown,
     pub stats: CellStats,
+    pub species: Species,
+    pub lineage: Lineage,
 }
 
 #[derive(Default, Bundle)]
@@ -194,43 +998,109 @@ pub struct ChunkBundle {
     pub position: Position,
     pub foodlist: Foodlist,
     pub chunk_settings: ChunkSettings,
+    pub pheromone: Pheromone,
+    pub food_spawn_factor: FoodSpawnFactor,
 }
 
-pub fn setup_chunks(mut commands: Commands) {
-    // Neues Chunk-Registry erstellen
-    let mut chunk_registry = ChunkRegistry {
-        chunk_size: 50.,
-        map_size: 40,
-        entries: Vec::new(),
-    };
+pub fn setup_chunks(mut commands: Commands, simulation_settings: Res<SimulationSettings>) {
+    // Neues Chunk-Registry mit neuen Chunks erstellen
+    let chunk_registry = ChunkRegistry::spawn(
+        &mut commands,
+        simulation_settings.chunk_size,
+        simulation_settings.map_width,
+        simulation_settings.map_height,
+        |_, _| default(),
+    );
 
-    // Neue Chunks erzeugen und Referenz im Chunk-Registry speichern
-    for index in 0..chunk_registry.map_size {
-        chunk_registry.entries.push(Vec::new());
-        for idy in 0..chunk_registry.map_size {
-            let chunk_entity = commands
-                .spawn(ChunkBundle {
-                    position: Position {
-                        x: (index as f32 * chunk_registry.chunk_size
-                            + chunk_registry.chunk_size / 2.),
-                        y: (idy as f32 * chunk_registry.chunk_size
-                            + chunk_registry.chunk_size / 2.),
-                    },
-                    ..default()
-                })
-                .id();
-            chunk_registry.entries[index as usize].push(chunk_entity);
+    // Chunk-Registry zu Welt hinzufügen
+    commands.spawn(chunk_registry);
+}
+
+/// Art des Objekts, das ein Sehstrahl zuerst trifft
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VisionHit {
+    Nothing,
+    Food,
+    Cell,
+    Wall,
+}
+
+impl VisionHit {
+    /// Wert, der in das Input-Neuron für die Art des getroffenen Objekts geschrieben wird
+    pub fn neuron_value(self) -> f32 {
+        match self {
+            Self::Nothing => 0.,
+            Self::Food => 1.,
+            Self::Cell => -1.,
+            Self::Wall => 0.5,
         }
     }
+}
 
-    // Chunk-Registry zu Welt hinzufügen
-    commands.spawn(chunk_registry);
+/// Distanz von `origin` in Richtung `direction` (normiert) bis zum Rand des Kreises um `center`.\
+/// [None], falls der Strahl den Kreis nicht trifft oder der Kreis hinter `origin` liegt.
+/// Liegt `origin` im Kreis, ist die Distanz 0.
+pub fn ray_circle_distance(
+    origin: &Position,
+    direction: (f32, f32),
+    center: &Position,
+    radius: f32,
+) -> Option<f32> {
+    let relative_position = Position {
+        x: center.x - origin.x,
+        y: center.y - origin.y,
+    };
+    let center_distance_squared =
+        relative_position.x * relative_position.x + relative_position.y * relative_position.y;
+    if center_distance_squared <= radius * radius {
+        return Some(0.);
+    }
+    // Abstand entlang des Strahls bis zum Punkt, der dem Mittelpunkt am nächsten ist
+    let closest_distance = relative_position.x * direction.0 + relative_position.y * direction.1;
+    let closest_center_distance_squared =
+        center_distance_squared - closest_distance * closest_distance;
+    if closest_distance < 0. || closest_center_distance_squared > radius * radius {
+        return None;
+    }
+    Some(closest_distance - (radius * radius - closest_center_distance_squared).sqrt())
+}
+
+/// Distanz von `origin` in Richtung `direction` (normiert) bis zur Strecke von `start` bis `end`.\
+/// [None], falls der Strahl die Strecke nicht trifft oder parallel zu ihr verläuft.
+pub fn ray_segment_distance(
+    origin: &Position,
+    direction: (f32, f32),
+    start: &Position,
+    end: &Position,
+) -> Option<f32> {
+    let segment = (end.x - start.x, end.y - start.y);
+    let denominator = direction.0 * segment.1 - direction.1 * segment.0;
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+    let relative_start = (start.x - origin.x, start.y - origin.y);
+    // Distanz entlang des Strahls und Anteil entlang der Strecke am Schnittpunkt
+    let distance = (relative_start.0 * segment.1 - relative_start.1 * segment.0) / denominator;
+    let factor = (relative_start.0 * direction.1 - relative_start.1 * direction.0) / denominator;
+    if distance < 0. || !(0. ..=1.).contains(&factor) {
+        return None;
+    }
+    Some(distance)
+}
+
+/// Zustand einer Zelle zu Beginn eines Ticks, den andere Zellen wahrnehmen können
+struct CellSnapshot {
+    entity: Entity,
+    position: Position,
+    radius: f32,
+    signals: Signals,
 }
 
 pub fn tick_cells(
     mut commands: Commands,
     mut cell_query: Query<
         (
+            Entity,
             &mut Brain,
             &mut Position,
             &mut Rotation,
@@ -238,6 +1108,10 @@ pub fn tick_cells(
             &mut Energy,
             &mut ChildCooldown,
             &mut CellStats,
+            &Species,
+            &Lineage,
+            &Genome,
+            &mut Signals,
         ),
         (With<Cell>, Without<Food>, Without<Chunk>),
     >,
@@ -269,11 +1143,28 @@ pub fn tick_cells(
             Without<ChildCountStatistic>,
         ),
     >,
-    chunk_query: Query<(&Foodlist, &ChunkSettings), (With<Chunk>, Without<Cell>, Without<Food>)>,
+    mut chunk_query: Query<
+        (&Foodlist, &ChunkSettings, &mut Pheromone),
+        (With<Chunk>, Without<Cell>, Without<Food>),
+    >,
     chunk_registry_query: Query<&ChunkRegistry>,
+    obstacle_query: Query<&Obstacle>,
     simulation_settings: Res<SimulationSettings>,
+    mutation_settings: Res<MutationSettings>,
+    sense_registry: Res<SenseRegistry>,
+    mut innovation_registry: ResMut<InnovationRegistry>,
+    (mut ancestry_log, mut remaining_energies): (ResMut<AncestryLog>, ResMut<RemainingEnergies>),
+    mut simulation_rng: ResMut<SimulationRng>,
+    tick_count: Res<TickCount>,
 ) {
     let chunk_registry = chunk_registry_query.single();
+    let world_topology = simulation_settings.world_topology;
+    // In der Nacht nehmen Zellen Nahrung und andere Objekte nur aus geringerer Entfernung wahr
+    let light_level = simulation_settings.light_level(**tick_count);
+    let sensing_factor =
+        SimulationSettings::daylight_factor(light_level, simulation_settings.night_sensing_factor);
+    let sensing_radius = simulation_settings.sensing_radius * sensing_factor;
+    let vision_range = simulation_settings.vision_range * sensing_factor;
 
     // Statistik Informationen deklarieren
     let mut cell_count = 0;
@@ -281,7 +1172,32 @@ pub fn tick_cells(
     let mut cells_born = 0;
     let mut neuron_count_sum = 0;
     let mut connection_count_sum = 0;
+    // Zellen, die sich in diesem Tick paaren wollen (nur bei `sexual_reproduction`)
+    let mut mating_candidates = Vec::new();
+    // Angriffe in diesem Tick (Angreifer, Ziel, Stärke)
+    let mut attacks = Vec::new();
+    // Pheromon, das in diesem Tick abgegeben wird (Chunk, Menge)
+    let mut pheromone_deposits = Vec::new();
+    // Zustand aller Zellen zu Beginn des Ticks, nach Chunk sortiert (für Berührungen, Sehstrahlen und Signale)
+    let mut cells_by_chunk: HashMap<Entity, Vec<CellSnapshot>> = HashMap::new();
+    let mut cell_radius_max: f32 = 0.;
+    for (entity, _, position, .., genome, signals) in &cell_query {
+        let radius = genome.radius(&simulation_settings);
+        cell_radius_max = cell_radius_max.max(radius);
+        if let Some(chunk_entity) = chunk_registry.chunk_at(position) {
+            cells_by_chunk
+                .entry(chunk_entity)
+                .or_default()
+                .push(CellSnapshot {
+                    entity,
+                    position: *position,
+                    radius,
+                    signals: *signals,
+                });
+        }
+    }
     for (
+        entity,
         mut brain,
         mut position,
         mut rotation,
@@ -289,9 +1205,20 @@ pub fn tick_cells(
         mut energy,
         mut child_cooldown,
         mut stats,
+        species,
+        lineage,
+        genome,
+        mut signals,
     ) in &mut cell_query
     {
+        let radius = genome.radius(&simulation_settings);
+
         let neuron_count = brain.neurons().len();
+        let hidden_neuron_count = brain
+            .neurons()
+            .iter()
+            .filter(|neuron| neuron.id >= FIRST_HIDDEN_NEURON_ID)
+            .count();
         let mut connection_count = 0;
         for neuron in brain.neurons() {
             connection_count += neuron.inputs.len();
@@ -306,69 +1233,273 @@ pub fn tick_cells(
         // Chunk berechnen
         let chunk_index = (position.x / chunk_registry.chunk_size) as i32;
         let chunk_idy = (position.y / chunk_registry.chunk_size) as i32;
+        let chunk_entity = chunk_registry.entries[chunk_index as usize][chunk_idy as usize];
         let chunk_settings = chunk_query
-            .get_component::<ChunkSettings>(
-                chunk_registry.entries[chunk_index as usize][chunk_idy as usize],
-            )
+            .get_component::<ChunkSettings>(chunk_entity)
             .unwrap();
 
-        // Inputs berechnen und in Input-Neuronen schreiben
-        let mut chunk_entities = Vec::with_capacity(9);
-        for jdx in -1..1 {
-            if chunk_index + jdx >= 0 && chunk_index + jdx < chunk_registry.map_size as i32 {
-                for jdy in -1..1 {
-                    if chunk_idy + jdy >= 0 && chunk_idy + jdy < chunk_registry.map_size as i32 {
-                        chunk_entities.push(
-                            chunk_registry.entries[(chunk_index + jdx) as usize]
-                                [(chunk_idy + jdy) as usize],
-                        );
-                    }
+        // Pheromon im eigenen Chunk und Gefälle zu den Nachbar-Chunks
+        // (am Rand der Map einseitig, bei `WorldTopology::Torus` über den Rand hinweg)
+        let pheromone_at = |index: i32, idy: i32| {
+            let wrap_index = |index: i32, map_size: u32| {
+                if world_topology == WorldTopology::Torus {
+                    index.rem_euclid(map_size as i32)
+                } else {
+                    index.clamp(0, map_size as i32 - 1)
                 }
-            }
-        }
-        let mut nearest_food_distance_squared = f32::INFINITY;
-        let mut nearest_food_position = Position::default();
-        for (foodlist, _) in chunk_query.iter_many(chunk_entities) {
+            };
+            chunk_query
+                .get_component::<Pheromone>(
+                    chunk_registry.entries[wrap_index(index, chunk_registry.map_size) as usize]
+                        [wrap_index(idy, chunk_registry.map_height) as usize],
+                )
+                .map_or(0., |pheromone| **pheromone)
+        };
+        let pheromone = pheromone_at(chunk_index, chunk_idy);
+        let pheromone_gradient_x = (pheromone_at(chunk_index + 1, chunk_idy)
+            - pheromone_at(chunk_index - 1, chunk_idy))
+            / 2.;
+        let pheromone_gradient_y = (pheromone_at(chunk_index, chunk_idy + 1)
+            - pheromone_at(chunk_index, chunk_idy - 1))
+            / 2.;
+
+        // Inputs berechnen und in Input-Neuronen schreiben
+        // Ohne Nahrung im (nachts verkleinerten) `sensing_radius` gilt die Nahrung als genau am Rand
+        // des ungeschwächten Radius vor der Zelle, damit Distanzen nachts gleich wahrgenommen werden
+        let sensing_radius_squared = sensing_radius * sensing_radius;
+        let mut nearest_food_distance_squared = sensing_radius_squared;
+        let mut nearest_food_relative_position = Position::default();
+        for (foodlist, ..) in chunk_query.iter_many(chunk_registry.chunks_in_radius(
+            &position,
+            sensing_radius,
+            world_topology,
+        )) {
             for (_, food_position, _) in food_query.iter_many(&**foodlist) {
-                let food_relative_position = Position {
-                    x: food_position.x - position.x,
-                    y: food_position.y - position.y,
-                };
+                let food_relative_position =
+                    chunk_registry.relative_position(&position, food_position, world_topology);
                 let distance_squared = food_relative_position.x * food_relative_position.x
                     + food_relative_position.y * food_relative_position.y;
                 if distance_squared < nearest_food_distance_squared {
                     nearest_food_distance_squared = distance_squared;
-                    nearest_food_position = *food_position;
+                    nearest_food_relative_position = food_relative_position;
+                }
+            }
+        }
+        let nearest_food_relative_angle = if nearest_food_distance_squared < sensing_radius_squared
+        {
+            // Winkel relativ zur Blickrichtung zwischen -PI und PI
+            let nearest_food_angle = nearest_food_relative_position
+                .y
+                .atan2(nearest_food_relative_position.x);
+            (nearest_food_angle - **rotation + PI).rem_euclid(2. * PI) - PI
+        } else {
+            nearest_food_distance_squared =
+                simulation_settings.sensing_radius * simulation_settings.sensing_radius;
+            0.
+        };
+        // Nächste Zelle, die diese Zelle berührt (Positionen vom Anfang des Ticks)
+        let mut nearest_touching_distance_squared = f32::INFINITY;
+        let mut nearest_touching_cell = None;
+        for chunk_entity in
+            chunk_registry.chunks_in_radius(&position, radius + cell_radius_max, world_topology)
+        {
+            for other in cells_by_chunk.get(&chunk_entity).into_iter().flatten() {
+                let relative_position =
+                    chunk_registry.relative_position(&position, &other.position, world_topology);
+                let distance_squared = relative_position.x * relative_position.x
+                    + relative_position.y * relative_position.y;
+                if other.entity != entity
+                    && distance_squared < (radius + other.radius) * (radius + other.radius)
+                    && distance_squared < nearest_touching_distance_squared
+                {
+                    nearest_touching_distance_squared = distance_squared;
+                    nearest_touching_cell = Some(other.entity);
+                }
+            }
+        }
+
+        // Signale der Nachbarn vom letzten Tick empfangen (linear schwächer bis `signal_range`)
+        let signal_channel_count = simulation_settings
+            .signal_channel_count
+            .min(SIGNAL_CHANNEL_COUNT_MAX as u32) as usize;
+        let mut received_signals = [0.; SIGNAL_CHANNEL_COUNT_MAX as usize];
+        if signal_channel_count > 0 {
+            for chunk_entity in chunk_registry.chunks_in_radius(
+                &position,
+                simulation_settings.signal_range,
+                world_topology,
+            ) {
+                for other in cells_by_chunk.get(&chunk_entity).into_iter().flatten() {
+                    let relative_position = chunk_registry.relative_position(
+                        &position,
+                        &other.position,
+                        world_topology,
+                    );
+                    let distance = (relative_position.x * relative_position.x
+                        + relative_position.y * relative_position.y)
+                        .sqrt();
+                    if other.entity == entity || distance >= simulation_settings.signal_range {
+                        continue;
+                    }
+                    let weight = 1. - distance / simulation_settings.signal_range;
+                    for (received_signal, signal) in received_signals
+                        .iter_mut()
+                        .zip(*other.signals)
+                        .take(signal_channel_count)
+                    {
+                        *received_signal += signal * weight;
+                    }
+                }
+            }
+        }
+
+        let mut perception = CellPerception {
+            nearest_food_angle: nearest_food_relative_angle,
+            nearest_food_distance_squared,
+            age: stats.age,
+            energy: **energy,
+            touching_cell: nearest_touching_cell.is_some(),
+            received_signals,
+            pheromone,
+            light_level,
+            // Gefälle relativ zur Blickrichtung (vorwärts, links)
+            pheromone_gradient: (
+                pheromone_gradient_x * rotation.cos() + pheromone_gradient_y * rotation.sin(),
+                pheromone_gradient_y * rotation.cos() - pheromone_gradient_x * rotation.sin(),
+            ),
+            vision: [(1., VisionHit::Nothing); VISION_RAY_COUNT_MAX as usize],
+        };
+
+        // Sehstrahlen fächerförmig um die Blickrichtung verteilen
+        // Strahlen über `vision_ray_count` sehen immer nichts
+        let vision_ray_count = simulation_settings
+            .vision_ray_count
+            .min(VISION_RAY_COUNT_MAX as u32);
+        let vision_chunks =
+            chunk_registry.chunks_in_radius(&position, vision_range, world_topology);
+        for ray_index in 0..VISION_RAY_COUNT_MAX as u32 {
+            let (mut hit_distance, mut hit) = (vision_range, VisionHit::Nothing);
+            if ray_index < vision_ray_count {
+                let ray_angle = if vision_ray_count > 1 {
+                    **rotation
+                        + simulation_settings.vision_angle_spread
+                            * (ray_index as f32 / (vision_ray_count - 1) as f32 - 0.5)
+                } else {
+                    **rotation
+                };
+                let direction = (ray_angle.cos(), ray_angle.sin());
+                // Nahrung und Zellen werden relativ zur Zelle getestet (für Distanzen über den Rand der Map)
+                let origin = Position::default();
+                for (foodlist, ..) in chunk_query.iter_many(&vision_chunks) {
+                    for (_, food_position, _) in food_query.iter_many(&**foodlist) {
+                        if let Some(distance) = ray_circle_distance(
+                            &origin,
+                            direction,
+                            &chunk_registry.relative_position(
+                                &position,
+                                food_position,
+                                world_topology,
+                            ),
+                            simulation_settings.food_radius,
+                        ) {
+                            if distance < hit_distance {
+                                (hit_distance, hit) = (distance, VisionHit::Food);
+                            }
+                        }
+                    }
+                }
+                for chunk_entity in &vision_chunks {
+                    for other in cells_by_chunk.get(chunk_entity).into_iter().flatten() {
+                        if other.entity == entity {
+                            continue;
+                        }
+                        if let Some(distance) = ray_circle_distance(
+                            &origin,
+                            direction,
+                            &chunk_registry.relative_position(
+                                &position,
+                                &other.position,
+                                world_topology,
+                            ),
+                            other.radius,
+                        ) {
+                            if distance < hit_distance {
+                                (hit_distance, hit) = (distance, VisionHit::Cell);
+                            }
+                        }
+                    }
+                }
+                // Auf einem Torus hat die Map keinen Rand
+                if world_topology != WorldTopology::Torus {
+                    let wall_distance = chunk_registry.ray_wall_distance(&position, direction);
+                    if wall_distance < hit_distance {
+                        (hit_distance, hit) = (wall_distance, VisionHit::Wall);
+                    }
+                }
+                // Hindernisse werden wie der Rand der Map als Wand gesehen
+                for obstacle in &obstacle_query {
+                    if let Some(distance) =
+                        obstacle.ray_distance(&position, direction, chunk_registry, world_topology)
+                    {
+                        if distance < hit_distance {
+                            (hit_distance, hit) = (distance, VisionHit::Wall);
+                        }
+                    }
                 }
             }
+            // Distanzen immer relativ zur ungeschwächten `vision_range` (nachts wird nur früher abgeschnitten)
+            perception.vision[ray_index as usize] = if hit == VisionHit::Nothing {
+                (1., hit)
+            } else {
+                (
+                    hit_distance / simulation_settings.vision_range.max(f32::EPSILON),
+                    hit,
+                )
+            };
         }
-        let nearest_food_angle = nearest_food_position.y.atan2(nearest_food_position.x);
-        let nearest_food_relative_angle = nearest_food_angle - **rotation;
-        brain.write_neuron(0, nearest_food_relative_angle);
-        brain.write_neuron(1, nearest_food_distance_squared);
-        brain.write_neuron(2, stats.age as f32);
-        brain.write_neuron(3, **energy as f32);
-        brain.write_neuron(4, (stats.age as f32 * 0.1).sin());
+        sense_registry.write_sensors(&mut brain, &perception);
 
         // Brain rechnen lassen
         brain.tick();
 
         // Output-Neuronen auslesen
-        let rotation_neuron_output = brain.read_neuron(5).unwrap();
-        let acceleration_neuron_output = brain.read_neuron(6).unwrap();
-        let want_child_neuron_output = brain.read_neuron(7).unwrap();
+        let actions = sense_registry.read_actuators(&brain);
+        let rotation_neuron_output = actions.rotation;
+        let acceleration_neuron_output = actions.acceleration;
+        let want_child_neuron_output = actions.want_child;
+
+        // Signale für den nächsten Tick senden (nicht verwendete Kanäle bleiben still)
+        for channel in 0..SIGNAL_CHANNEL_COUNT_MAX as usize {
+            signals[channel] = if channel < signal_channel_count {
+                actions.signals[channel]
+            } else {
+                0.
+            };
+        }
+
+        // Pheromon abgeben (wird erst eingetragen, wenn alle Zellen gerechnet haben)
+        if actions.pheromone_deposit > 0. {
+            pheromone_deposits.push((
+                chunk_entity,
+                actions.pheromone_deposit.min(1.) * simulation_settings.pheromone_deposit_rate,
+            ));
+        }
+
+        // Angreifen (Energie wird erst abgezogen, wenn alle Zellen gerechnet haben)
+        if actions.attack > 0. {
+            **energy -= simulation_settings.attack_cost;
+            if let Some(target_entity) = nearest_touching_cell {
+                attacks.push((entity, target_entity, actions.attack));
+            }
+        }
 
         // Rotieren und Geschwindigkeit passend verändern
-        **rotation += rotation_neuron_output * simulation_settings.rotation_speed_max;
+        **rotation +=
+            rotation_neuron_output * simulation_settings.rotation_speed_max * genome.speed;
+        let acceleration_max = simulation_settings.acceleration_max * genome.speed;
         let new_velocity = Velocity {
-            x: velocity.x
-                + rotation.cos()
-                    * acceleration_neuron_output
-                    * simulation_settings.acceleration_max,
-            y: velocity.y
-                + rotation.sin()
-                    * acceleration_neuron_output
-                    * simulation_settings.acceleration_max,
+            x: velocity.x + rotation.cos() * acceleration_neuron_output * acceleration_max,
+            y: velocity.y + rotation.sin() * acceleration_neuron_output * acceleration_max,
         };
 
         // Kinetische Energie berechnen und von Energie abziehen
@@ -378,15 +1509,57 @@ pub fn tick_cells(
 
         // Geschwindikeit und Position berechen
         *velocity = new_velocity;
-        position.x += velocity.x;
-        position.y += velocity.y;
-        if position.x < 0.
-            || position.y < 0.
-            || position.x >= (chunk_registry.map_size as f32 * chunk_registry.chunk_size) as f32
-            || position.y >= (chunk_registry.map_size as f32 * chunk_registry.chunk_size) as f32
+        // Vor dem ersten Hindernis auf dem Weg anhalten, damit schnelle Zellen nicht durch Wände springen
+        // (`collide_obstacles` schiebt sie danach aus dem Hindernis und stoppt die Bewegung hinein)
+        let movement_length = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+        let mut movement_factor: f32 = 1.;
+        if movement_length > 0. {
+            let direction = (velocity.x / movement_length, velocity.y / movement_length);
+            for obstacle in &obstacle_query {
+                if let Some(distance) = obstacle.sweep_distance(
+                    &position,
+                    direction,
+                    radius,
+                    chunk_registry,
+                    world_topology,
+                ) {
+                    movement_factor = movement_factor.min(distance / movement_length);
+                }
+            }
+        }
+        position.x += velocity.x * movement_factor;
+        position.y += velocity.y * movement_factor;
+        let (map_width, map_height) = chunk_registry.map_length();
+        if position.x < 0. || position.y < 0. || position.x >= map_width || position.y >= map_height
         {
-            **energy = 0.;
-            continue;
+            match world_topology {
+                WorldTopology::BoundedLethal => {
+                    remaining_energies.insert(entity, **energy);
+                    **energy = 0.;
+                    continue;
+                }
+                WorldTopology::BoundedReflective => {
+                    // Am Rand spiegeln und Geschwindigkeit senkrecht zum Rand umkehren
+                    if position.x < 0. || position.x >= map_width {
+                        velocity.x = -velocity.x;
+                        position.x = if position.x < 0. {
+                            -position.x
+                        } else {
+                            2. * map_width - position.x
+                        };
+                    }
+                    if position.y < 0. || position.y >= map_height {
+                        velocity.y = -velocity.y;
+                        position.y = if position.y < 0. {
+                            -position.y
+                        } else {
+                            2. * map_height - position.y
+                        };
+                    }
+                    chunk_registry.keep_on_map(&mut position, world_topology);
+                }
+                WorldTopology::Torus => chunk_registry.keep_on_map(&mut position, world_topology),
+            }
         }
         velocity.x *= 1. - chunk_settings.velocity_damping;
         velocity.y *= 1. - chunk_settings.velocity_damping;
@@ -394,36 +1567,24 @@ pub fn tick_cells(
         // Essen einsammeln
 
         // Benötigte Distanz berechen (squared um sqrt(x) zu vermeiden)
-        let distance_min_squared = (simulation_settings.cell_radius
-            + simulation_settings.food_radius)
-            * (simulation_settings.cell_radius + simulation_settings.food_radius);
+        let distance_min_squared =
+            (radius + simulation_settings.food_radius) * (radius + simulation_settings.food_radius);
 
         // Tatsächliche Kollisionen berechnen
-        let mut chunk_entities = Vec::with_capacity(9);
-        for jdx in -1..1 {
-            if chunk_index + jdx >= 0 && chunk_index + jdx < chunk_registry.map_size as i32 {
-                for jdy in -1..1 {
-                    if chunk_idy + jdy >= 0 && chunk_idy + jdy < chunk_registry.map_size as i32 {
-                        chunk_entities.push(
-                            chunk_registry.entries[(chunk_index + jdx) as usize]
-                                [(chunk_idy + jdy) as usize],
-                        );
-                    }
-                }
-            }
-        }
-        for (foodlist, _) in chunk_query.iter_many(chunk_entities) {
+        for (foodlist, ..) in chunk_query.iter_many(chunk_registry.chunks_in_radius(
+            &position,
+            radius + simulation_settings.food_radius,
+            world_topology,
+        )) {
             let mut food_query_iter = food_query.iter_many_mut(&**foodlist);
             while let Some((_, food_position, mut food_energy)) = food_query_iter.fetch_next() {
-                let relative_position = Position {
-                    x: food_position.x - position.x,
-                    y: food_position.y - position.y,
-                };
+                let relative_position =
+                    chunk_registry.relative_position(&position, &food_position, world_topology);
                 let distance_squared = relative_position.x * relative_position.x
                     + relative_position.y * relative_position.y;
                 if distance_squared < distance_min_squared {
                     // Essen leersaugen
-                    **energy += **food_energy;
+                    **energy += **food_energy * genome.metabolism;
                     **food_energy = 0.;
                 }
             }
@@ -434,35 +1595,49 @@ pub fn tick_cells(
             && **energy > simulation_settings.energy_required_for_split
             && **child_cooldown == 0
         {
-            // Stats aktualisieren
-            cells_born += 1;
-            stats.child_count += 1;
-
-            **child_cooldown = simulation_settings.child_cooldown;
-
-            // Child-Brain erstellen
-            let mut child_brain = brain.clone();
-            child_brain.mutate();
-
-            // Neuen Energiewerte berechnen
-            let new_energy = **energy / 2.;
-            **energy = new_energy;
-
-            // Kind in Welt spawnen
-            commands.spawn(CellBundle {
-                position: Position {
-                    x: position.x,
-                    y: position.y,
-                },
-                rotation: Rotation(**rotation),
-                energy: Energy(new_energy),
-                brain: child_brain,
-                child_cooldown: ChildCooldown(simulation_settings.child_cooldown),
-                ..default()
-            });
+            if simulation_settings.sexual_reproduction {
+                // Partner wird erst gesucht, wenn alle Zellen bewegt wurden
+                mating_candidates.push((entity, *position));
+            } else {
+                // Stats aktualisieren
+                cells_born += 1;
+                stats.child_count += 1;
+
+                **child_cooldown = simulation_settings.child_cooldown;
+
+                // Child-Genome und Child-Brain erstellen (mit der Mutationsrate des Kindes)
+                let mut child_genome = genome.clone();
+                child_genome.mutate(&mutation_settings, &mut **simulation_rng);
+                let mut child_brain = brain.clone();
+                child_brain.mutate(
+                    &mutation_settings.scaled(child_genome.mutation_rate),
+                    &mut innovation_registry,
+                    &mut **simulation_rng,
+                );
+
+                // Neuen Energiewerte berechnen
+                let new_energy = **energy / 2.;
+                **energy = new_energy;
+
+                // Kind in Welt spawnen
+                commands.spawn(CellBundle {
+                    position: Position {
+                        x: position.x,
+                        y: position.y,
+                    },
+                    rotation: Rotation(**rotation),
+                    energy: Energy(new_energy),
+                    brain: child_brain,
+                    genome: child_genome,
+                    child_cooldown: ChildCooldown(simulation_settings.child_cooldown),
+                    species: *species,
+                    lineage: ancestry_log.record_birth(Some(lineage), None, **tick_count),
+                    ..default()
+                });
+            }
         }
-        **energy -= simulation_settings.base_energy_drain
-            + neuron_count as f32 * simulation_settings.neuron_energy_drain
+        **energy -= genome.energy_drain(&simulation_settings)
+            + hidden_neuron_count as f32 * simulation_settings.neuron_energy_drain
             + connection_count as f32 * simulation_settings.connection_energy_drain
             + stats.age as f32 * simulation_settings.age_energy_drain;
         stats.age += 1;
@@ -471,6 +1646,146 @@ pub fn tick_cells(
         }
     }
 
+    for (chunk_entity, amount) in pheromone_deposits {
+        if let Ok(mut pheromone) = chunk_query.get_component_mut::<Pheromone>(chunk_entity) {
+            **pheromone += amount;
+        }
+    }
+
+    // Angriffe auflösen: das Ziel verliert bis zu `attack_drain` Energie,
+    // davon bekommt der Angreifer den Anteil `attack_efficiency`
+    for (attacker_entity, target_entity, attack_strength) in attacks {
+        let Ok(mut target_energy) = cell_query.get_component_mut::<Energy>(target_entity) else {
+            continue;
+        };
+        let drained_energy = (simulation_settings.attack_drain * attack_strength)
+            .min(**target_energy)
+            .max(0.);
+        **target_energy -= drained_energy;
+        // Was der Angreifer vom tödlichen Angriff nicht bekommt, bleibt im Kadaver
+        if **target_energy <= 0. {
+            *remaining_energies.entry(target_entity).or_default() +=
+                drained_energy * (1. - simulation_settings.attack_efficiency);
+        }
+        if let Ok(mut attacker_energy) = cell_query.get_component_mut::<Energy>(attacker_entity) {
+            **attacker_energy += drained_energy * simulation_settings.attack_efficiency;
+        }
+    }
+
+    // Paare bilden: jeder Kandidat paart sich mit dem nächsten noch freien Kandidaten in `mating_radius`
+    let mating_radius_squared =
+        simulation_settings.mating_radius * simulation_settings.mating_radius;
+    let mut is_paired = vec![false; mating_candidates.len()];
+    for candidate_index in 0..mating_candidates.len() {
+        if is_paired[candidate_index] {
+            continue;
+        }
+        let (candidate_entity, candidate_position) = mating_candidates[candidate_index];
+        let mut nearest_partner_index = None;
+        let mut nearest_partner_distance_squared = mating_radius_squared;
+        for partner_index in (candidate_index + 1)..mating_candidates.len() {
+            if is_paired[partner_index] {
+                continue;
+            }
+            let partner_position = mating_candidates[partner_index].1;
+            let relative_position = chunk_registry.relative_position(
+                &candidate_position,
+                &partner_position,
+                world_topology,
+            );
+            let distance_squared = relative_position.x * relative_position.x
+                + relative_position.y * relative_position.y;
+            if distance_squared <= nearest_partner_distance_squared {
+                nearest_partner_distance_squared = distance_squared;
+                nearest_partner_index = Some(partner_index);
+            }
+        }
+        let Some(partner_index) = nearest_partner_index else {
+            continue;
+        };
+        is_paired[candidate_index] = true;
+        is_paired[partner_index] = true;
+        let partner_entity = mating_candidates[partner_index].0;
+        let [(
+            _,
+            brain_a,
+            position_a,
+            rotation_a,
+            _,
+            mut energy_a,
+            mut child_cooldown_a,
+            mut stats_a,
+            species_a,
+            lineage_a,
+            genome_a,
+            _,
+        ), (
+            _,
+            brain_b,
+            position_b,
+            _,
+            _,
+            mut energy_b,
+            mut child_cooldown_b,
+            mut stats_b,
+            species_b,
+            lineage_b,
+            genome_b,
+            _,
+        )] = cell_query.many_mut([candidate_entity, partner_entity]);
+
+        // Stats aktualisieren
+        cells_born += 1;
+        stats_a.child_count += 1;
+        stats_b.child_count += 1;
+
+        **child_cooldown_a = simulation_settings.child_cooldown;
+        **child_cooldown_b = simulation_settings.child_cooldown;
+
+        // Child-Brain erstellen (der Elternteil mit mehr Energie gibt den Aufbau und die Species vor)
+        let (mut child_brain, child_species, child_lineage) = if **energy_a >= **energy_b {
+            (
+                brain_a.crossover(&brain_b, &mut **simulation_rng),
+                *species_a,
+                ancestry_log.record_birth(Some(lineage_a), Some(lineage_b), **tick_count),
+            )
+        } else {
+            (
+                brain_b.crossover(&brain_a, &mut **simulation_rng),
+                *species_b,
+                ancestry_log.record_birth(Some(lineage_b), Some(lineage_a), **tick_count),
+            )
+        };
+        let mut child_genome = genome_a.crossover(genome_b, &mut **simulation_rng);
+        child_genome.mutate(&mutation_settings, &mut **simulation_rng);
+        child_brain.mutate(
+            &mutation_settings.scaled(child_genome.mutation_rate),
+            &mut innovation_registry,
+            &mut **simulation_rng,
+        );
+
+        // Beide Eltern geben die Hälfte ihrer Energie an das Kind ab
+        let child_energy = **energy_a / 2. + **energy_b / 2.;
+        **energy_a /= 2.;
+        **energy_b /= 2.;
+
+        // Kind zwischen den Eltern in Welt spawnen
+        commands.spawn(CellBundle {
+            position: Position {
+                x: (position_a.x + position_b.x) / 2.,
+                y: (position_a.y + position_b.y) / 2.,
+            },
+            rotation: Rotation(**rotation_a),
+            energy: Energy(child_energy),
+            brain: child_brain,
+            genome: child_genome,
+            child_cooldown: ChildCooldown(simulation_settings.child_cooldown),
+            species: child_species,
+            lineage: child_lineage,
+            ..default()
+        });
+    }
+
     // Statistiken schreiben
     cell_count_statistic_query.single_mut().lines[0]
         .data_points
@@ -497,41 +1812,374 @@ pub fn tick_cells(
     }
 }
 
+/// Berechnet den [FoodSpawnFactor] aller Chunks für den aktuellen Tick
+/// (nachts spawnt abhängig von `night_food_spawn_factor` weniger Nahrung)
+pub fn apply_environment_schedule(
+    mut chunk_query: Query<(&Position, &mut FoodSpawnFactor), With<Chunk>>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    environment_schedule: Res<EnvironmentSchedule>,
+    simulation_settings: Res<SimulationSettings>,
+    tick_count: Res<TickCount>,
+) {
+    let chunk_registry = chunk_registry_query.single();
+    let daylight_factor = SimulationSettings::daylight_factor(
+        simulation_settings.light_level(**tick_count),
+        simulation_settings.night_food_spawn_factor,
+    );
+    for (position, mut food_spawn_factor) in &mut chunk_query {
+        **food_spawn_factor = daylight_factor
+            * environment_schedule.food_spawn_factor(position, **tick_count, chunk_registry);
+    }
+}
+
 pub fn spawn_food(
     mut commands: Commands,
-    mut chunk_query: Query<(&mut Foodlist, &ChunkSettings, &Position), With<Chunk>>,
+    mut chunk_query: Query<
+        (&mut Foodlist, &ChunkSettings, &FoodSpawnFactor, &Position),
+        With<Chunk>,
+    >,
     chunk_registry_query: Query<&ChunkRegistry>,
+    obstacle_query: Query<&Obstacle>,
+    mut simulation_rng: ResMut<SimulationRng>,
+    simulation_settings: Res<SimulationSettings>,
 ) {
     let chunk_registry = chunk_registry_query.single();
+    // In massiven Hindernissen wäre Nahrung unerreichbar
+    let is_blocked = |position: &Position| {
+        obstacle_query.iter().any(|obstacle| {
+            obstacle
+                .contact(
+                    position,
+                    0.,
+                    chunk_registry,
+                    simulation_settings.world_topology,
+                )
+                .is_some()
+        })
+    };
 
-    for (mut foodlist, chunk_settings, chunk_position) in &mut chunk_query {
-        let mut to_place = chunk_settings.spawn_chance;
+    for (mut foodlist, chunk_settings, food_spawn_factor, chunk_position) in &mut chunk_query {
+        let mut to_place = chunk_settings.spawn_chance * **food_spawn_factor;
         while to_place > 1. {
-            let food_entity = commands
-                .spawn(FoodBundle {
-                    position: Position {
-                        x: chunk_position.x + (random::<f32>() - 0.5) * chunk_registry.chunk_size,
-                        y: chunk_position.y + (random::<f32>() - 0.5) * chunk_registry.chunk_size,
-                    },
-                    energy: Energy(chunk_settings.spawned_food_energy),
-                    ..default()
-                })
-                .id();
-            foodlist.push(food_entity);
+            let position = Position {
+                x: chunk_position.x
+                    + (simulation_rng.gen::<f32>() - 0.5) * chunk_registry.chunk_size,
+                y: chunk_position.y
+                    + (simulation_rng.gen::<f32>() - 0.5) * chunk_registry.chunk_size,
+            };
+            if !is_blocked(&position) {
+                let food_entity = commands
+                    .spawn(FoodBundle {
+                        position,
+                        energy: Energy(chunk_settings.spawned_food_energy),
+                        ..default()
+                    })
+                    .id();
+                foodlist.push(food_entity);
+            }
             to_place -= 1.;
         }
-        if random::<f32>() < to_place {
-            let food_entity = commands
-                .spawn(FoodBundle {
-                    position: Position {
-                        x: chunk_position.x + (random::<f32>() - 0.5) * chunk_registry.chunk_size,
-                        y: chunk_position.y + (random::<f32>() - 0.5) * chunk_registry.chunk_size,
-                    },
-                    energy: Energy(chunk_settings.spawned_food_energy),
-                    ..default()
+        if simulation_rng.gen::<f32>() < to_place {
+            let position = Position {
+                x: chunk_position.x
+                    + (simulation_rng.gen::<f32>() - 0.5) * chunk_registry.chunk_size,
+                y: chunk_position.y
+                    + (simulation_rng.gen::<f32>() - 0.5) * chunk_registry.chunk_size,
+            };
+            if !is_blocked(&position) {
+                let food_entity = commands
+                    .spawn(FoodBundle {
+                        position,
+                        energy: Energy(chunk_settings.spawned_food_energy),
+                        ..default()
+                    })
+                    .id();
+                foodlist.push(food_entity);
+            }
+        }
+    }
+}
+
+/// Lässt sich überlappende Zellen voneinander abprallen.\
+/// Die Überlappung wird abhängig von der Masse ([Genome]) aufgelöst (leichte Zellen werden weiter geschoben)
+/// und die Geschwindigkeiten entlang der Stoßrichtung ändern sich wie bei einem Stoß zweier Körper
+/// (abhängig von `cell_collision_elasticity`).
+pub fn collide_cells(
+    mut cell_query: Query<(Entity, &mut Position, &mut Velocity, &Genome), With<Cell>>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+) {
+    if !simulation_settings.cell_collisions {
+        return;
+    }
+    let chunk_registry = chunk_registry_query.single();
+    let world_topology = simulation_settings.world_topology;
+
+    // Zellen nach Chunk sortieren
+    let cells: Vec<(Entity, Position, Velocity, f32, f32)> = cell_query
+        .iter()
+        .map(|(entity, position, velocity, genome)| {
+            (
+                entity,
+                *position,
+                *velocity,
+                genome.radius(&simulation_settings),
+                genome.mass(),
+            )
+        })
+        .collect();
+    let cell_radius_max = cells.iter().fold(0., |radius_max: f32, (.., radius, _)| {
+        radius_max.max(*radius)
+    });
+    let mut cells_by_chunk: HashMap<Entity, Vec<usize>> = HashMap::new();
+    for (cell_index, (_, position, ..)) in cells.iter().enumerate() {
+        if let Some(chunk_entity) = chunk_registry.chunk_at(position) {
+            cells_by_chunk
+                .entry(chunk_entity)
+                .or_default()
+                .push(cell_index);
+        }
+    }
+
+    // Kollisionen berechnen (jedes Paar nur einmal)
+    let mut position_changes = vec![Position::default(); cells.len()];
+    let mut velocity_changes = vec![Velocity::default(); cells.len()];
+    for (cell_index, (_, position, velocity, radius, mass)) in cells.iter().enumerate() {
+        for chunk_entity in
+            chunk_registry.chunks_in_radius(position, radius + cell_radius_max, world_topology)
+        {
+            for &other_index in cells_by_chunk.get(&chunk_entity).into_iter().flatten() {
+                if other_index <= cell_index {
+                    continue;
+                }
+                let (_, other_position, other_velocity, other_radius, other_mass) =
+                    &cells[other_index];
+                let collision_distance = radius + other_radius;
+                let relative_position =
+                    chunk_registry.relative_position(position, other_position, world_topology);
+                let distance = (relative_position.x * relative_position.x
+                    + relative_position.y * relative_position.y)
+                    .sqrt();
+                if distance >= collision_distance || distance <= f32::EPSILON {
+                    continue;
+                }
+                let normal = (
+                    relative_position.x / distance,
+                    relative_position.y / distance,
+                );
+
+                // Überlappung auflösen
+                let overlap = collision_distance - distance;
+                let share = other_mass / (mass + other_mass);
+                position_changes[cell_index].x -= normal.0 * overlap * share;
+                position_changes[cell_index].y -= normal.1 * overlap * share;
+                position_changes[other_index].x += normal.0 * overlap * (1. - share);
+                position_changes[other_index].y += normal.1 * overlap * (1. - share);
+
+                // Impuls austauschen, falls sich die Zellen aufeinander zu bewegen
+                let approach_speed = (other_velocity.x - velocity.x) * normal.0
+                    + (other_velocity.y - velocity.y) * normal.1;
+                if approach_speed < 0. {
+                    let impulse = -(1. + simulation_settings.cell_collision_elasticity)
+                        * approach_speed
+                        / (1. / mass + 1. / other_mass);
+                    velocity_changes[cell_index].x -= normal.0 * impulse / mass;
+                    velocity_changes[cell_index].y -= normal.1 * impulse / mass;
+                    velocity_changes[other_index].x += normal.0 * impulse / other_mass;
+                    velocity_changes[other_index].y += normal.1 * impulse / other_mass;
+                }
+            }
+        }
+    }
+
+    // Änderungen anwenden (Zellen werden nicht aus der Map geschoben)
+    for (cell_index, (entity, ..)) in cells.iter().enumerate() {
+        let Ok((_, mut position, mut velocity, _)) = cell_query.get_mut(*entity) else {
+            continue;
+        };
+        position.x += position_changes[cell_index].x;
+        position.y += position_changes[cell_index].y;
+        chunk_registry.keep_on_map(&mut position, world_topology);
+        velocity.x += velocity_changes[cell_index].x;
+        velocity.y += velocity_changes[cell_index].y;
+    }
+}
+
+/// Schiebt Zellen aus [Obstacle]s heraus und stoppt ihre Bewegung in das Hindernis hinein
+/// (entlang des Hindernisses können sie weiter gleiten).
+pub fn collide_obstacles(
+    mut cell_query: Query<(&mut Position, &mut Velocity, &Genome), With<Cell>>,
+    obstacle_query: Query<&Obstacle>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+) {
+    if obstacle_query.is_empty() {
+        return;
+    }
+    let chunk_registry = chunk_registry_query.single();
+
+    for (mut position, mut velocity, genome) in &mut cell_query {
+        let radius = genome.radius(&simulation_settings);
+        for obstacle in &obstacle_query {
+            let Some((normal, depth)) = obstacle.contact(
+                &position,
+                radius,
+                chunk_registry,
+                simulation_settings.world_topology,
+            ) else {
+                continue;
+            };
+            position.x += normal.0 * depth;
+            position.y += normal.1 * depth;
+            chunk_registry.keep_on_map(&mut position, simulation_settings.world_topology);
+            let normal_speed = velocity.x * normal.0 + velocity.y * normal.1;
+            if normal_speed < 0. {
+                velocity.x -= normal.0 * normal_speed;
+                velocity.y -= normal.1 * normal_speed;
+            }
+        }
+    }
+}
+
+/// Schreibt die durchschnittliche `mutation_rate` aus den [Genome]s aller Zellen in die Mutation Rate Statistik
+pub fn write_mutation_rate_statistic(
+    cell_query: Query<&Genome, With<Cell>>,
+    mut mutation_rate_statistic_query: Query<&mut StatisticData, With<MutationRateStatistic>>,
+) {
+    // Ältere Saves haben noch keine Mutation Rate Statistik
+    let Ok(mut statistic_data) = mutation_rate_statistic_query.get_single_mut() else {
+        return;
+    };
+    let cell_count = cell_query.iter().len();
+    if cell_count > 0 {
+        let mutation_rate_sum: f32 = cell_query.iter().map(|genome| genome.mutation_rate).sum();
+        statistic_data.lines[0]
+            .data_points
+            .push(mutation_rate_sum / cell_count as f32);
+    }
+}
+
+/// Fügt Zellen und Chunks aus alten Saves die Komponenten hinzu, die es damals noch nicht gab
+/// ([Genome], [Signals], [Species] und [Lineage] bzw. [Pheromone]).
+/// Ohne sie würden die Zellen von `tick_cells` und `despawn_cells` übersprungen.
+pub fn insert_missing_components(
+    mut commands: Commands,
+    cell_query: Query<
+        (
+            Entity,
+            Option<&Genome>,
+            Option<&Signals>,
+            Option<&Species>,
+            Option<&Lineage>,
+        ),
+        (
+            With<Cell>,
+            Or<(
+                Without<Genome>,
+                Without<Signals>,
+                Without<Species>,
+                Without<Lineage>,
+            )>,
+        ),
+    >,
+    chunk_query: Query<
+        (Entity, Option<&Pheromone>, Option<&FoodSpawnFactor>),
+        (
+            With<Chunk>,
+            Or<(Without<Pheromone>, Without<FoodSpawnFactor>)>,
+        ),
+    >,
+    mut chunk_registry_query: Query<&mut ChunkRegistry>,
+    mut ancestry_log: ResMut<AncestryLog>,
+    tick_count: Res<TickCount>,
+) {
+    for (entity, pheromone, food_spawn_factor) in &chunk_query {
+        if pheromone.is_none() {
+            commands.entity(entity).insert(Pheromone::default());
+        }
+        if food_spawn_factor.is_none() {
+            commands.entity(entity).insert(FoodSpawnFactor::default());
+        }
+    }
+    // Ältere Saves haben nur quadratische Maps ohne `map_height`
+    for mut chunk_registry in &mut chunk_registry_query {
+        if chunk_registry.map_height == 0 {
+            chunk_registry.map_height = chunk_registry.entries.first().map_or(0, Vec::len) as u32;
+        }
+    }
+    for (entity, genome, signals, species, lineage) in &cell_query {
+        if genome.is_none() {
+            commands.entity(entity).insert(Genome::default());
+        }
+        if signals.is_none() {
+            commands.entity(entity).insert(Signals::default());
+        }
+        // Wird von `update_species` bei der nächsten Einteilung einer Species zugeordnet
+        if species.is_none() {
+            commands.entity(entity).insert(Species::default());
+        }
+        // Die Vorfahren sind unbekannt, die Zelle beginnt also eine neue Abstammungslinie
+        if lineage.is_none() {
+            commands
+                .entity(entity)
+                .insert(ancestry_log.record_birth(None, None, **tick_count));
+        }
+    }
+}
+
+/// Verteilt das [Pheromone] jedes Chunks auf seine (bis zu vier) Nachbar-Chunks und lässt es zerfallen.\
+/// Jeder Chunk gleicht sich pro Tick um `pheromone_diffusion_rate` an den Durchschnitt seiner Nachbarn an,
+/// dabei bleibt die Gesamtmenge erhalten (auch am Rand der Map, bei [WorldTopology::Torus] fließt es über den Rand).
+pub fn update_pheromones(
+    mut chunk_query: Query<&mut Pheromone, With<Chunk>>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+) {
+    let chunk_registry = chunk_registry_query.single();
+    let diffusion_rate = simulation_settings.pheromone_diffusion_rate.clamp(0., 1.);
+    let decay_factor = 1. - simulation_settings.pheromone_decay_rate.clamp(0., 1.);
+
+    // Werte vom Anfang des Ticks, damit die Reihenfolge der Chunks keine Rolle spielt
+    let values: Vec<Vec<f32>> = chunk_registry
+        .entries
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .map(|chunk_entity| {
+                    chunk_query
+                        .get(*chunk_entity)
+                        .map_or(0., |pheromone| **pheromone)
                 })
-                .id();
-            foodlist.push(food_entity);
+                .collect()
+        })
+        .collect();
+    for (index, column) in chunk_registry.entries.iter().enumerate() {
+        for (idy, chunk_entity) in column.iter().enumerate() {
+            let Ok(mut pheromone) = chunk_query.get_mut(*chunk_entity) else {
+                continue;
+            };
+            let value = values[index][idy];
+            let mut flow = 0.;
+            for (offset_x, offset_y) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (mut neighbour_index, mut neighbour_idy) =
+                    (index as i32 + offset_x, idy as i32 + offset_y);
+                if simulation_settings.world_topology == WorldTopology::Torus {
+                    neighbour_index = neighbour_index.rem_euclid(values.len() as i32);
+                    neighbour_idy = neighbour_idy.rem_euclid(column.len() as i32);
+                }
+                if let Some(neighbour_value) = usize::try_from(neighbour_index)
+                    .ok()
+                    .and_then(|neighbour_index| values.get(neighbour_index))
+                    .zip(usize::try_from(neighbour_idy).ok())
+                    .and_then(|(neighbour_column, neighbour_idy)| {
+                        neighbour_column.get(neighbour_idy)
+                    })
+                {
+                    flow += (neighbour_value - value) * diffusion_rate / 4.;
+                }
+            }
+            **pheromone = (value + flow) * decay_factor;
         }
     }
 }
@@ -548,41 +2196,322 @@ pub fn despawn_food(mut commands: Commands, food_query: Query<(Entity, &Energy),
 pub fn despawn_cells(
     mut commands: Commands,
     mut cell_count_statistic_query: Query<&mut StatisticData, With<CellCountStatistic>>,
-    cell_query: Query<(Entity, &Energy), With<Cell>>,
+    mut foodlist_query: Query<&mut Foodlist, With<Chunk>>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    mut ancestry_log: ResMut<AncestryLog>,
+    mut remaining_energies: ResMut<RemainingEnergies>,
+    cell_query: Query<(Entity, &Energy, &Lineage, &Position), With<Cell>>,
+    simulation_settings: Res<SimulationSettings>,
+    tick_count: Res<TickCount>,
 ) {
+    let chunk_registry = chunk_registry_query.single();
+    let (map_width, map_height) = chunk_registry.map_length();
+
     // Zellen ohne Energie löschen
     let mut cells_died = 0;
-    for (entity, energy) in &cell_query {
+    for (entity, energy, lineage, position) in &cell_query {
         if **energy <= 0. {
             cells_died += 1;
+            ancestry_log.record_death(lineage, **tick_count);
             commands.entity(entity).despawn();
+
+            // Kadaver hinterlassen (Zellen, die über den Rand geflogen sind, am Rand)
+            if !simulation_settings.leave_corpses {
+                continue;
+            }
+            let corpse_position = Position {
+                x: position.x.clamp(0., map_width - 0.01),
+                y: position.y.clamp(0., map_height - 0.01),
+            };
+            let Some(chunk_entity) = chunk_registry.chunk_at(&corpse_position) else {
+                continue;
+            };
+            let corpse_energy = remaining_energies
+                .get(&entity)
+                .map_or(simulation_settings.corpse_body_energy, |remaining_energy| {
+                    remaining_energy.max(simulation_settings.corpse_body_energy)
+                });
+            let corpse_entity = commands
+                .spawn(FoodBundle {
+                    position: corpse_position,
+                    energy: Energy(corpse_energy),
+                    ..default()
+                })
+                .id();
+            foodlist_query
+                .get_mut(chunk_entity)
+                .unwrap()
+                .push(corpse_entity);
         }
     }
 
+    remaining_energies.clear();
+
     // Statistiken schreiben
     cell_count_statistic_query.single_mut().lines[2]
         .data_points
         .push(cells_died as f32);
 }
 
+/// Anzahl an Ticks, die eine ausgestorbene [Species] noch in der Species Statistik bleibt
+pub const EXTINCT_SPECIES_GRACE_PERIOD: usize = 1000;
+
+/// Teilt alle `speciation_interval` Ticks die Zellen in [Species] ein und schreibt die Species Statistik.
+/// Ausgestorbene Species werden nach `EXTINCT_SPECIES_GRACE_PERIOD` Ticks aus der Statistik entfernt.
+///
+/// Jede bestehende Species wird durch das [Brain] ihrer ersten Zelle repräsentiert.
+/// Eine Zelle bleibt in ihrer Species, solange sie dem Repräsentanten ähnlich genug ist,
+/// sonst kommt sie in die erste passende Species oder gründet eine neue.
+pub fn update_species(
+    mut cell_query: Query<(&Brain, &mut Species), With<Cell>>,
+    mut species_statistic_query: Query<(&mut StatisticData, &mut SpeciesStatistic)>,
+    mut species_registry: ResMut<SpeciesRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+    tick_count: Res<TickCount>,
+) {
+    // Zellen ohne Species werden sofort eingeteilt, alle anderen nur alle `speciation_interval` Ticks
+    let is_speciation_tick = simulation_settings.speciation_interval > 0
+        && **tick_count % simulation_settings.speciation_interval as u64 == 0;
+    if simulation_settings.speciation_interval > 0
+        && (is_speciation_tick || cell_query.iter().any(|(_, species)| species.id == 0))
+    {
+        // Repräsentanten der bestehenden Species sammeln
+        let mut representatives: Vec<(Species, Brain)> = Vec::new();
+        for (brain, species) in &cell_query {
+            if species.id != 0
+                && !representatives
+                    .iter()
+                    .any(|(representative_species, _)| representative_species.id == species.id)
+            {
+                representatives.push((*species, brain.clone()));
+            }
+        }
+        representatives.sort_by_key(|(species, _)| species.id);
+
+        // Zellen einteilen
+        let is_compatible = |brain: &Brain, representative_brain: &Brain| {
+            brain.compatibility_distance(
+                representative_brain,
+                simulation_settings.species_disjoint_coefficient,
+                simulation_settings.species_weight_coefficient,
+            ) <= simulation_settings.species_compatibility_threshold
+        };
+        for (brain, mut species) in &mut cell_query {
+            if !is_speciation_tick && species.id != 0 {
+                continue;
+            }
+            let stays_in_species =
+                representatives
+                    .iter()
+                    .any(|(representative_species, representative_brain)| {
+                        representative_species.id == species.id
+                            && is_compatible(brain, representative_brain)
+                    });
+            if stays_in_species {
+                continue;
+            }
+            let compatible_species = representatives
+                .iter()
+                .find(|(_, representative_brain)| is_compatible(brain, representative_brain))
+                .map(|(representative_species, _)| *representative_species);
+            *species = match compatible_species {
+                Some(compatible_species) => compatible_species,
+                None => {
+                    let new_species = species_registry.new_species();
+                    representatives.push((new_species, brain.clone()));
+                    new_species
+                }
+            };
+        }
+    }
+
+    // Zellen pro Species zählen
+    let mut species_counts = BTreeMap::new();
+    for (_, species) in &cell_query {
+        if species.id != 0 {
+            species_counts
+                .entry(species.id)
+                .or_insert((0, species.color))
+                .0 += 1;
+        }
+    }
+
+    // Statistiken schreiben (ausgestorbene Species bekommen noch einen letzten Datenpunkt mit 0)
+    let (mut statistic_data, mut species_statistic) = species_statistic_query.single_mut();
+    let data_point_index = species_statistic.data_point_count;
+    for (line_index, species_id) in species_statistic.species_ids.iter().enumerate() {
+        let line = &mut statistic_data.lines[line_index];
+        if let Some((count, _)) = species_counts.remove(species_id) {
+            line.data_points.push(count as f32);
+        } else if line.start_index + line.data_points.len() == data_point_index
+            && line.data_points.last() != Some(&0.)
+        {
+            line.data_points.push(0.);
+        }
+    }
+    // Linien von Species, die schon länger ausgestorben sind, entfernen (damit lange Läufe nicht immer mehr Linien ansammeln)
+    for line_index in (0..species_statistic.species_ids.len()).rev() {
+        let line = &statistic_data.lines[line_index];
+        if line.start_index + line.data_points.len() + EXTINCT_SPECIES_GRACE_PERIOD
+            <= data_point_index
+        {
+            species_statistic.species_ids.remove(line_index);
+            statistic_data.lines.remove(line_index);
+        }
+    }
+    for (species_id, (count, color)) in species_counts {
+        species_statistic.species_ids.push(species_id);
+        statistic_data.lines.push(StatisticLine {
+            legend_name: format!("Species {}", species_id),
+            color,
+            start_index: data_point_index,
+            data_points: vec![count as f32],
+        });
+    }
+    species_statistic.data_point_count += 1;
+}
+
+/// Zustand von `run_on_tick` zwischen zwei Aufrufen
+#[derive(Default)]
+pub struct TickClock {
+    /// Zeit seit dem ersten Tick des letzten Frames, in dem getickt wurde
+    tick_watch: Stopwatch,
+    /// Zeitpunkt des letzten Time-Updates, um neue Frames zu erkennen
+    last_update: Option<Instant>,
+    /// Zeitpunkt an dem im aktuellen Frame zum ersten Mal geprüft wurde
+    frame_start: Option<Instant>,
+    ticks_this_frame: u32,
+    /// Zeit und Ticks seit der letzten Messung der Ticks pro Sekunde
+    ticks_per_second_watch: Stopwatch,
+    ticks_since_measurement: u32,
+    /// Anzahl an Ticks, nach denen wegen `Step` oder `RunTicks` pausiert wird
+    remaining_ticks: Option<u64>,
+}
+
+/// Run-Criteria für die Tick Systeme.
+/// Wird innerhalb eines Frames so lange erneut ausgewertet, bis `ticks_per_frame` Ticks durchgeführt
+/// wurden oder `frame_time_budget_seconds` aufgebraucht ist.\
+/// Ist zugleich Event-Handler für `Step` und `RunTicks` Events.
 pub fn run_on_tick(
-    mut tick_watch: Local<Stopwatch>,
+    mut tick_clock: Local<TickClock>,
+    mut step_events: EventReader<Step>,
+    mut run_ticks_events: EventReader<RunTicks>,
     mut control_center_ui: ResMut<ControlCenterUi>,
-    simulation_settings: Res<SimulationSettings>,
+    mut tick_count: ResMut<TickCount>,
+    mut simulation_settings: ResMut<SimulationSettings>,
     time: Res<Time>,
 ) -> ShouldRun {
+    // Neuen Frame erkennen
+    if time.last_update() != tick_clock.last_update {
+        tick_clock.last_update = time.last_update();
+        tick_clock.frame_start = Some(Instant::now());
+        tick_clock.ticks_this_frame = 0;
+        tick_clock.tick_watch.tick(time.delta());
+
+        // Ticks pro Sekunde ungefähr einmal pro Sekunde messen
+        if tick_clock
+            .ticks_per_second_watch
+            .tick(time.delta())
+            .elapsed_secs()
+            >= 1.
+        {
+            control_center_ui.ticks_per_second_label = format!(
+                "{:.0}",
+                tick_clock.ticks_since_measurement as f32
+                    / tick_clock.ticks_per_second_watch.elapsed_secs()
+            );
+            tick_clock.ticks_per_second_watch.reset();
+            tick_clock.ticks_since_measurement = 0;
+        }
+    }
+
+    // Simulation für eine bestimmte Anzahl an Ticks fortsetzen
+    for _ in step_events.iter() {
+        tick_clock.remaining_ticks = Some(1);
+        simulation_settings.is_paused = false;
+    }
+    for run_ticks_event in run_ticks_events.iter() {
+        if run_ticks_event.tick_count > 0 {
+            tick_clock.remaining_ticks = Some(run_ticks_event.tick_count);
+            simulation_settings.is_paused = false;
+        }
+    }
+
     // Prüfen ob ein Tick aussteht
-    if !simulation_settings.is_paused
-        && tick_watch.tick(time.delta()).elapsed_secs() >= simulation_settings.tick_delta_seconds
-    {
-        control_center_ui.actual_tick_delta_seconds_label =
-            format!("{:.3}", tick_watch.elapsed_secs());
-        tick_watch.reset();
-        // Tick in Auftrag geben
-        ShouldRun::Yes
+    if simulation_settings.is_paused {
+        // Manuelles Pausieren bricht `Step` und `RunTicks` ab
+        tick_clock.remaining_ticks = None;
+        return ShouldRun::No;
+    }
+    let is_tick_due = if tick_clock.ticks_this_frame == 0 {
+        tick_clock.tick_watch.elapsed_secs() >= simulation_settings.tick_delta_seconds
     } else {
+        tick_clock.ticks_this_frame < simulation_settings.ticks_per_frame
+            && tick_clock.frame_start.unwrap().elapsed().as_secs_f32()
+                < simulation_settings.frame_time_budget_seconds
+    };
+    if !is_tick_due {
         // Keinen Tick in Auftrag geben
-        ShouldRun::No
+        return ShouldRun::No;
+    }
+
+    if tick_clock.ticks_this_frame == 0 {
+        control_center_ui.actual_tick_delta_seconds_label =
+            format!("{:.3}", tick_clock.tick_watch.elapsed_secs());
+        tick_clock.tick_watch.reset();
+    }
+    tick_clock.ticks_this_frame += 1;
+    tick_clock.ticks_since_measurement += 1;
+    **tick_count += 1;
+    if let Some(remaining_ticks) = &mut tick_clock.remaining_ticks {
+        *remaining_ticks = remaining_ticks.saturating_sub(1);
+        if *remaining_ticks == 0 {
+            // Nach diesem Tick pausieren
+            tick_clock.remaining_ticks = None;
+            simulation_settings.is_paused = true;
+            return ShouldRun::Yes;
+        }
+    }
+
+    // Tick in Auftrag geben (und danach prüfen, ob in diesem Frame noch ein Tick möglich ist)
+    if tick_clock.ticks_this_frame < simulation_settings.ticks_per_frame {
+        ShouldRun::YesAndCheckAgain
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+/// Baut die [SenseRegistry] neu, wenn sich `signal_channel_count` geändert hat, und passt alle [Brain]s daran an.
+/// Neuronen von nicht mehr verwendeten Kanälen bleiben mit ihren Connections im [Brain] erhalten.
+pub fn update_sense_registry(
+    mut brain_query: Query<&mut Brain>,
+    mut sense_registry: ResMut<SenseRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+) {
+    let signal_channel_count = simulation_settings
+        .signal_channel_count
+        .min(SIGNAL_CHANNEL_COUNT_MAX as u32);
+    if !simulation_settings.is_changed()
+        || sense_registry.signal_channel_count() == signal_channel_count
+    {
+        return;
+    }
+    *sense_registry = SenseRegistry::with_signal_channels(signal_channel_count);
+    for mut brain in &mut brain_query {
+        brain.adapt_to_senses(&sense_registry);
+    }
+}
+
+/// Passt neue (z.B. geladene) [Brain]s an die [SenseRegistry] an.\
+/// [Brain]s aus älteren Saves bekommen vorher ihre fehlenden Ids und Innovationsnummern.
+pub fn adapt_brains(
+    mut brain_query: Query<&mut Brain, Added<Brain>>,
+    sense_registry: Res<SenseRegistry>,
+    mut innovation_registry: ResMut<InnovationRegistry>,
+) {
+    for mut brain in &mut brain_query {
+        brain.assign_missing_ids(&mut innovation_registry);
+        brain.adapt_to_senses(&sense_registry);
     }
 }
 
@@ -594,28 +2523,110 @@ pub struct SpawnCell {
 pub fn spawn_cells(
     mut commands: Commands,
     mut spawn_cell_events: EventReader<SpawnCell>,
+    mut simulation_rng: ResMut<SimulationRng>,
     chunk_registry_query: Query<&ChunkRegistry>,
+    mutation_settings: Res<MutationSettings>,
+    sense_registry: Res<SenseRegistry>,
+    mut innovation_registry: ResMut<InnovationRegistry>,
+    mut ancestry_log: ResMut<AncestryLog>,
+    tick_count: Res<TickCount>,
 ) {
     for spawn_cell_event in spawn_cell_events.iter() {
         let chunk_registry = chunk_registry_query.single();
-        let mut brain = Brain::new();
-        brain.mutate();
+        let (map_width, map_height) = chunk_registry.map_length();
+        let mut brain = Brain::new(&sense_registry);
+        brain.mutate(
+            &mutation_settings,
+            &mut innovation_registry,
+            &mut **simulation_rng,
+        );
         commands.spawn(CellBundle {
             position: Position {
-                x: random::<f32>() * chunk_registry.map_size as f32 * chunk_registry.chunk_size,
-                y: random::<f32>() * chunk_registry.map_size as f32 * chunk_registry.chunk_size,
+                x: simulation_rng.gen::<f32>() * map_width,
+                y: simulation_rng.gen::<f32>() * map_height,
             },
-            rotation: Rotation(random::<f32>() * 2. * PI),
+            rotation: Rotation(simulation_rng.gen::<f32>() * 2. * PI),
             energy: Energy(spawn_cell_event.energy),
             brain,
+            lineage: ancestry_log.record_birth(None, None, **tick_count),
             ..default()
         });
     }
 }
 
+pub struct SpawnObstacle {
+    pub obstacle: Obstacle,
+}
+
+/// Event-Handler für `SpawnObstacle` Events
+pub fn spawn_obstacles(
+    mut commands: Commands,
+    mut spawn_obstacle_events: EventReader<SpawnObstacle>,
+) {
+    for spawn_obstacle_event in spawn_obstacle_events.iter() {
+        commands.spawn(spawn_obstacle_event.obstacle);
+    }
+}
+
+/// Lädt die [Obstacle]s aus einer RON-Datei zusätzlich zu den bestehenden
+pub struct LoadObstacles {
+    pub path: String,
+}
+
+/// Event-Handler für `LoadObstacles` Events
+pub fn load_obstacles(
+    mut commands: Commands,
+    mut load_obstacles_events: EventReader<LoadObstacles>,
+) {
+    for load_obstacles_event in load_obstacles_events.iter() {
+        let path = &load_obstacles_event.path;
+        let obstacles: Vec<Obstacle> = match fs::read_to_string(Path::new(path))
+            .map_err(|error| error.to_string())
+            .and_then(|serialized| ron::from_str(&serialized).map_err(|error| error.to_string()))
+        {
+            Ok(obstacles) => obstacles,
+            Err(error) => {
+                eprintln!("Could not load obstacles from {}: {}", path, error);
+                continue;
+            }
+        };
+        for obstacle in obstacles {
+            commands.spawn(obstacle);
+        }
+    }
+}
+
+pub struct LoadEnvironmentSchedule {
+    pub path: String,
+}
+
+/// Event-Handler für `LoadEnvironmentSchedule` Events
+pub fn load_environment_schedule(
+    mut environment_schedule: ResMut<EnvironmentSchedule>,
+    mut load_environment_schedule_events: EventReader<LoadEnvironmentSchedule>,
+) {
+    for load_environment_schedule_event in load_environment_schedule_events.iter() {
+        let path = &load_environment_schedule_event.path;
+        match fs::read_to_string(Path::new(path))
+            .map_err(|error| error.to_string())
+            .and_then(|serialized| ron::from_str(&serialized).map_err(|error| error.to_string()))
+        {
+            Ok(loaded_environment_schedule) => *environment_schedule = loaded_environment_schedule,
+            Err(error) => {
+                eprintln!(
+                    "Could not load environment schedule from {}: {}",
+                    path, error
+                )
+            }
+        }
+    }
+}
+
 pub struct Clear {
     pub clear_food: bool,
     pub clear_cells: bool,
+    pub clear_obstacles: bool,
+    pub clear_environment_schedule: bool,
     pub clear_statistics: bool,
 }
 
@@ -623,9 +2634,16 @@ pub struct Clear {
 pub fn clear(
     mut commands: Commands,
     mut clear_events: EventReader<Clear>,
-    mut statistic_query: Query<&mut StatisticData, With<Statistic>>,
+    mut statistic_query: Query<
+        (&mut StatisticData, Option<&mut SpeciesStatistic>),
+        With<Statistic>,
+    >,
     food_query: Query<Entity, With<Food>>,
-    cell_query: Query<Entity, With<Cell>>,
+    cell_query: Query<(Entity, &Lineage), With<Cell>>,
+    obstacle_query: Query<Entity, With<Obstacle>>,
+    mut ancestry_log: ResMut<AncestryLog>,
+    mut environment_schedule: ResMut<EnvironmentSchedule>,
+    tick_count: Res<TickCount>,
 ) {
     for clear_event in clear_events.iter() {
         if clear_event.clear_food {
@@ -634,20 +2652,355 @@ pub fn clear(
             }
         }
         if clear_event.clear_cells {
-            for entity in &cell_query {
+            for (entity, lineage) in &cell_query {
+                ancestry_log.record_death(lineage, **tick_count);
                 commands.entity(entity).despawn();
             }
         }
+        if clear_event.clear_obstacles {
+            for entity in &obstacle_query {
+                commands.entity(entity).despawn();
+            }
+        }
+        if clear_event.clear_environment_schedule {
+            *environment_schedule = EnvironmentSchedule::default();
+        }
         if clear_event.clear_statistics {
-            for mut statistic_data in &mut statistic_query {
-                for mut statistic_line in &mut statistic_data.lines {
+            for (mut statistic_data, species_statistic) in &mut statistic_query {
+                for statistic_line in &mut statistic_data.lines {
                     statistic_line.data_points = Vec::new();
+                    statistic_line.start_index = 0;
+                }
+                // Linien der Species Statistik werden beim nächsten Tick neu angelegt
+                if let Some(mut species_statistic) = species_statistic {
+                    statistic_data.lines = Vec::new();
+                    *species_statistic = SpeciesStatistic::default();
+                }
+            }
+        }
+    }
+}
+
+pub struct CreateWorld {
+    /// Map Breite in Chunks
+    pub map_width: u32,
+    /// Map Höhe in Chunks
+    pub map_height: u32,
+    pub chunk_size: f32,
+}
+
+/// Event-Handler für `CreateWorld` Event.\
+/// Ersetzt die Chunks durch eine neue Map. Jeder neue Chunk übernimmt [ChunkSettings] und [Pheromone]
+/// von dem alten Chunk an der gleichen relativen Stelle, Zellen, Nahrung und Hindernisse
+/// werden im Verhältnis der Map-Größen auf die neue Map verschoben.\
+/// Muss mit `.at_start()` registriert werden: Die [ChunkRegistry] zeigt danach auf die neuen Chunks,
+/// die erst mit den Commands entstehen. Die Tick Systeme der gleichen Stage dürfen also erst nach
+/// dem Anwenden der Commands laufen.
+pub fn create_world(
+    mut commands: Commands,
+    mut create_world_events: EventReader<CreateWorld>,
+    mut chunk_registry_query: Query<&mut ChunkRegistry>,
+    chunk_query: Query<(&ChunkSettings, &Pheromone), With<Chunk>>,
+    mut position_query: Query<(Entity, &mut Position, Option<&Food>), Or<(With<Cell>, With<Food>)>>,
+    mut obstacle_query: Query<&mut Obstacle>,
+    mut simulation_settings: ResMut<SimulationSettings>,
+) {
+    for create_world_event in create_world_events.iter() {
+        let map_width = create_world_event.map_width.max(1);
+        let map_height = create_world_event.map_height.max(1);
+        let chunk_size = create_world_event.chunk_size.max(1.);
+        let mut chunk_registry = chunk_registry_query.single_mut();
+        let (old_map_width, old_map_height) = chunk_registry.map_length();
+        let (new_map_width, new_map_height) = (
+            map_width as f32 * chunk_size,
+            map_height as f32 * chunk_size,
+        );
+        let scale = |position: &mut Position| {
+            position.x =
+                (position.x / old_map_width * new_map_width).clamp(0., new_map_width - 0.01);
+            position.y =
+                (position.y / old_map_height * new_map_height).clamp(0., new_map_height - 0.01);
+        };
+
+        // Zellen und Nahrung verschieben, Nahrung wird in die Foodlist ihres neuen Chunks eingetragen
+        let mut foodlists = vec![vec![Vec::new(); map_height as usize]; map_width as usize];
+        for (entity, mut position, food) in &mut position_query {
+            scale(&mut position);
+            if food.is_some() {
+                let index = ((position.x / chunk_size) as usize).min(map_width as usize - 1);
+                let idy = ((position.y / chunk_size) as usize).min(map_height as usize - 1);
+                foodlists[index][idy].push(entity);
+            }
+        }
+        for mut obstacle in &mut obstacle_query {
+            match &mut *obstacle {
+                Obstacle::Circle { center, .. } => scale(center),
+                Obstacle::Segment { start, end } => {
+                    scale(start);
+                    scale(end);
+                }
+            }
+        }
+
+        // Index des alten Chunks, der an der gleichen relativen Stelle liegt (gemessen an der Mitte des neuen Chunks)
+        let old_index = |index: u32, map_size: u32, old_map_size: u32| {
+            ((index as f32 + 0.5) / map_size as f32 * old_map_size as f32) as usize
+        };
+
+        // Neue Chunks erzeugen, alte Chunks despawnen
+        let new_chunk_registry = ChunkRegistry::spawn(
+            &mut commands,
+            chunk_size,
+            map_width,
+            map_height,
+            |index, idy| {
+                let old_chunk_entity = chunk_registry.entries
+                    [old_index(index, map_width, chunk_registry.map_size)]
+                    [old_index(idy, map_height, chunk_registry.map_height)];
+                let (chunk_settings, pheromone) = chunk_query
+                    .get(old_chunk_entity)
+                    .map(|(chunk_settings, pheromone)| (chunk_settings.clone(), *pheromone))
+                    .unwrap_or_default();
+                ChunkBundle {
+                    foodlist: Foodlist(std::mem::take(
+                        &mut foodlists[index as usize][idy as usize],
+                    )),
+                    chunk_settings,
+                    pheromone,
+                    ..default()
+                }
+            },
+        );
+        for chunk_entity in chunk_registry.entries.iter().flatten() {
+            commands.entity(*chunk_entity).despawn();
+        }
+        *chunk_registry = new_chunk_registry;
+
+        simulation_settings.map_width = map_width;
+        simulation_settings.map_height = map_height;
+        simulation_settings.chunk_size = chunk_size;
+    }
+}
+
+pub struct PaintChunkSettings {
+    /// Mitte des Pinsels
+    pub center: Position,
+    pub radius: f32,
+    /// Anteil zwischen 0 und 1 des Radius, über den die Wirkung nach außen hin auf 0 abfällt
+    /// (0 ist ein harter Rand)
+    pub falloff: f32,
+    /// Wie lange in Sekunden gemalt wurde (bei gedrückter Maus die Dauer des Frames).
+    /// Nach einer Sekunde Malen ist ein Chunk um die Wirkung [PaintChunkSettings::strength] an die Werte herangezogen.
+    pub duration: f32,
+    /// Werte, auf die die [ChunkSettings] gesetzt werden ([None] lässt den Wert unverändert)
+    pub spawn_chance: Option<f32>,
+    pub spawned_food_energy: Option<f32>,
+    pub velocity_damping: Option<f32>,
+}
+
+impl PaintChunkSettings {
+    /// Wirkung zwischen 0 und 1 des Pinsels auf einen Chunk, dessen Mitte `distance` von der Mitte des Pinsels entfernt ist
+    pub fn strength(&self, distance: f32) -> f32 {
+        let inner_radius = self.radius * (1. - self.falloff.clamp(0., 1.));
+        if distance <= inner_radius {
+            1.
+        } else if distance >= self.radius {
+            0.
+        } else {
+            (self.radius - distance) / (self.radius - inner_radius)
+        }
+    }
+}
+
+/// Event-Handler für `PaintChunkSettings` Event.\
+/// Zieht die [ChunkSettings] der Chunks unter dem Pinsel abhängig von der Wirkung und der Dauer an die Werte
+/// des Pinsels heran (bei längerem Malen nähern sich auch die Chunks am Rand den Werten an, unabhängig von der Framerate).
+/// Der Chunk direkt unter der Mitte des Pinsels wird immer vollständig gesetzt.
+pub fn paint_chunk_settings(
+    mut chunk_query: Query<(Entity, &mut ChunkSettings, &Position), With<Chunk>>,
+    mut paint_chunk_settings_events: EventReader<PaintChunkSettings>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    simulation_settings: Res<SimulationSettings>,
+) {
+    let world_topology = simulation_settings.world_topology;
+    for paint_chunk_settings_event in paint_chunk_settings_events.iter() {
+        let chunk_registry = chunk_registry_query.single();
+        let center_chunk_entity = chunk_registry.chunk_at(&paint_chunk_settings_event.center);
+        let mut chunk_query_iter = chunk_query.iter_many_mut(chunk_registry.chunks_in_radius(
+            &paint_chunk_settings_event.center,
+            paint_chunk_settings_event.radius,
+            world_topology,
+        ));
+        while let Some((chunk_entity, mut chunk_settings, chunk_position)) =
+            chunk_query_iter.fetch_next()
+        {
+            let strength = if Some(chunk_entity) == center_chunk_entity {
+                1.
+            } else {
+                let relative_position = chunk_registry.relative_position(
+                    &paint_chunk_settings_event.center,
+                    chunk_position,
+                    world_topology,
+                );
+                let strength = paint_chunk_settings_event.strength(
+                    (relative_position.x * relative_position.x
+                        + relative_position.y * relative_position.y)
+                        .sqrt(),
+                );
+                // Mehrere kurze Pinselstriche wirken zusammen wie ein langer
+                1. - (1. - strength).powf(paint_chunk_settings_event.duration.max(0.))
+            };
+            if strength <= 0. {
+                continue;
+            }
+            let paint = |value: &mut f32, target: Option<f32>| {
+                if let Some(target) = target {
+                    *value += (target - *value) * strength;
+                }
+            };
+            paint(
+                &mut chunk_settings.spawn_chance,
+                paint_chunk_settings_event.spawn_chance,
+            );
+            paint(
+                &mut chunk_settings.spawned_food_energy,
+                paint_chunk_settings_event.spawned_food_energy,
+            );
+            paint(
+                &mut chunk_settings.velocity_damping,
+                paint_chunk_settings_event.velocity_damping,
+            );
+        }
+    }
+}
+
+/// Werte der [ChunkSettings] bei einem Kanal-Wert von 255 in Chunk Settings Bildern
+/// (`velocity_damping` reicht immer von 0 bis 1).\
+/// Kanäle: Rot ist `spawn_chance`, Grün `spawned_food_energy` und Blau `velocity_damping`.
+/// Graustufen-Bilder enthalten nur die `spawn_chance`.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkSettingsImageScale {
+    pub spawn_chance_max: f32,
+    pub spawned_food_energy_max: f32,
+}
+
+impl Default for ChunkSettingsImageScale {
+    fn default() -> Self {
+        Self {
+            spawn_chance_max: 0.1,
+            spawned_food_energy_max: 400.,
+        }
+    }
+}
+
+pub struct ImportChunkSettings {
+    /// Pfad zu einem PNG, PGM oder PPM Bild
+    pub path: String,
+    pub scale: ChunkSettingsImageScale,
+}
+
+/// Event-Handler für `ImportChunkSettings` Events.\
+/// Das Bild wird auf die Map gestreckt, jeder Chunk übernimmt den Pixel unter seiner Mitte.
+/// Die oberste Zeile des Bildes ist der obere Rand der Map.
+pub fn import_chunk_settings(
+    mut chunk_query: Query<&mut ChunkSettings, With<Chunk>>,
+    mut import_chunk_settings_events: EventReader<ImportChunkSettings>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+) {
+    for import_chunk_settings_event in import_chunk_settings_events.iter() {
+        let path = &import_chunk_settings_event.path;
+        let image = match image::open(Path::new(path)) {
+            Ok(image) => image,
+            Err(error) => {
+                eprintln!("Could not load chunk settings from {}: {}", path, error);
+                continue;
+            }
+        };
+        let is_grayscale = !image.color().has_color();
+        let image = image.to_rgb8();
+        let scale = import_chunk_settings_event.scale;
+        let chunk_registry = chunk_registry_query.single();
+        for (index, column) in chunk_registry.entries.iter().enumerate() {
+            for (idy, chunk_entity) in column.iter().enumerate() {
+                let Ok(mut chunk_settings) = chunk_query.get_mut(*chunk_entity) else {
+                    continue;
+                };
+                let pixel_x =
+                    (index as f32 + 0.5) / chunk_registry.map_size as f32 * image.width() as f32;
+                let pixel_y = (chunk_registry.map_height as f32 - idy as f32 - 0.5)
+                    / chunk_registry.map_height as f32
+                    * image.height() as f32;
+                let [red, green, blue] = image
+                    .get_pixel(
+                        (pixel_x as u32).min(image.width() - 1),
+                        (pixel_y as u32).min(image.height() - 1),
+                    )
+                    .0;
+                chunk_settings.spawn_chance = red as f32 / 255. * scale.spawn_chance_max;
+                if !is_grayscale {
+                    chunk_settings.spawned_food_energy =
+                        green as f32 / 255. * scale.spawned_food_energy_max;
+                    chunk_settings.velocity_damping = blue as f32 / 255.;
                 }
             }
         }
     }
 }
 
+pub struct ExportChunkSettings {
+    /// Pfad zu einem PNG, PGM oder PPM Bild (das Format ergibt sich aus der Dateiendung)
+    pub path: String,
+    pub scale: ChunkSettingsImageScale,
+}
+
+/// Event-Handler für `ExportChunkSettings` Events.\
+/// Schreibt ein Bild mit einem Pixel pro Chunk (als PGM nur die `spawn_chance`).
+pub fn export_chunk_settings(
+    chunk_query: Query<&ChunkSettings, With<Chunk>>,
+    mut export_chunk_settings_events: EventReader<ExportChunkSettings>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+) {
+    for export_chunk_settings_event in export_chunk_settings_events.iter() {
+        let scale = export_chunk_settings_event.scale;
+        let chunk_registry = chunk_registry_query.single();
+        let channel =
+            |value: f32, value_max: f32| (value / value_max * 255.).round().clamp(0., 255.) as u8;
+        let image = image::RgbImage::from_fn(
+            chunk_registry.map_size,
+            chunk_registry.map_height,
+            |pixel_x, pixel_y| {
+                let chunk_entity = chunk_registry.entries[pixel_x as usize]
+                    [(chunk_registry.map_height - pixel_y - 1) as usize];
+                let chunk_settings = chunk_query.get(chunk_entity).cloned().unwrap_or_default();
+                image::Rgb([
+                    channel(chunk_settings.spawn_chance, scale.spawn_chance_max),
+                    channel(
+                        chunk_settings.spawned_food_energy,
+                        scale.spawned_food_energy_max,
+                    ),
+                    channel(chunk_settings.velocity_damping, 1.),
+                ])
+            },
+        );
+        let path = &export_chunk_settings_event.path;
+        let is_pgm = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pgm"));
+        let result = if is_pgm {
+            // Nur der rote Kanal (`spawn_chance`) als Graustufen
+            image::GrayImage::from_fn(image.width(), image.height(), |pixel_x, pixel_y| {
+                image::Luma([image.get_pixel(pixel_x, pixel_y).0[0]])
+            })
+            .save(path)
+        } else {
+            image.save(path)
+        };
+        if let Err(error) = result {
+            eprintln!("Could not export chunk settings to {}: {}", path, error);
+        }
+    }
+}
+
 pub struct ApplyChunkSettings;
 
 /// Event-Handler für `ApplyChunkSettings` Event
@@ -664,8 +3017,8 @@ pub fn apply_chunk_settings(
     for _ in apply_chunk_settings_events.iter() {
         let chunk_registry = chunk_registry_query.single();
         for (mut chunk_settings, chunk_position) in &mut chunk_query {
-            let chunk_index = (chunk_position.x / chunk_registry.chunk_size) as f32;
-            let chunk_idy = (chunk_position.y / chunk_registry.chunk_size) as f32;
+            let chunk_index = chunk_position.x / chunk_registry.chunk_size;
+            let chunk_idy = chunk_position.y / chunk_registry.chunk_size;
             *chunk_settings = ChunkSettings {
                 spawn_chance: spawn_chance_left
                     + (spawn_chance_right - spawn_chance_left) * chunk_index
@@ -673,7 +3026,7 @@ pub fn apply_chunk_settings(
                 spawned_food_energy: control_center_ui.food_energy_drag_value,
                 velocity_damping: velocity_damping_bottom
                     + (velocity_damping_top - velocity_damping_bottom) * chunk_idy
-                        / chunk_registry.map_size as f32,
+                        / chunk_registry.map_height as f32,
             };
         }
     }
@@ -684,29 +3037,106 @@ pub struct ApplySimulationSettings;
 /// Event-Handler für `ApplySimulationSettings` Event
 pub fn apply_simulation_settings(
     mut simulation_settings: ResMut<SimulationSettings>,
+    mut simulation_rng: ResMut<SimulationRng>,
     mut apply_simulation_settings_events: EventReader<ApplySimulationSettings>,
     control_center_ui: Res<ControlCenterUi>,
 ) {
     for _ in apply_simulation_settings_events.iter() {
+        let old_seed = simulation_settings.seed;
         *simulation_settings = SimulationSettings {
+            seed: control_center_ui.seed_drag_value,
             cell_radius: control_center_ui.cell_radius_drag_value,
             food_radius: control_center_ui.food_radius_drag_value,
             tick_delta_seconds: control_center_ui.tick_delta_seconds_slider,
+            ticks_per_frame: control_center_ui.ticks_per_frame_slider,
+            frame_time_budget_seconds: control_center_ui.frame_time_budget_seconds_slider,
             base_energy_drain: control_center_ui.base_energy_drain_drag_value,
             neuron_energy_drain: control_center_ui.neuron_energy_drain_drag_value,
             connection_energy_drain: control_center_ui.connection_energy_drain_drag_value,
             age_energy_drain: control_center_ui.age_energy_drain_drag_value,
             energy_required_for_split: control_center_ui.energy_required_for_split_drag_value,
             child_cooldown: control_center_ui.child_cooldown_drag_value,
+            sexual_reproduction: control_center_ui.sexual_reproduction_checkbox,
+            mating_radius: control_center_ui.mating_radius_drag_value,
+            sensing_radius: control_center_ui.sensing_radius_drag_value,
+            // Die Map wird nur mit `CreateWorld` geändert
+            map_width: simulation_settings.map_width,
+            map_height: simulation_settings.map_height,
+            chunk_size: simulation_settings.chunk_size,
+            world_topology: control_center_ui.world_topology_combo_box,
+            cell_collisions: control_center_ui.cell_collisions_checkbox,
+            cell_collision_elasticity: control_center_ui.cell_collision_elasticity_slider,
+            attack_drain: control_center_ui.attack_drain_drag_value,
+            attack_efficiency: control_center_ui.attack_efficiency_slider,
+            attack_cost: control_center_ui.attack_cost_drag_value,
+            leave_corpses: control_center_ui.leave_corpses_checkbox,
+            corpse_body_energy: control_center_ui.corpse_body_energy_drag_value,
+            vision_ray_count: control_center_ui.vision_ray_count_slider,
+            vision_angle_spread: control_center_ui.vision_angle_spread_slider,
+            vision_range: control_center_ui.vision_range_drag_value,
+            signal_channel_count: control_center_ui.signal_channel_count_slider,
+            signal_range: control_center_ui.signal_range_drag_value,
+            pheromone_deposit_rate: control_center_ui.pheromone_deposit_rate_drag_value,
+            pheromone_diffusion_rate: control_center_ui.pheromone_diffusion_rate_slider,
+            pheromone_decay_rate: control_center_ui.pheromone_decay_rate_slider,
+            day_length: control_center_ui.day_length_drag_value,
+            night_food_spawn_factor: control_center_ui.night_food_spawn_factor_slider,
+            night_sensing_factor: control_center_ui.night_sensing_factor_slider,
+            speciation_interval: control_center_ui.speciation_interval_drag_value,
+            species_compatibility_threshold: control_center_ui
+                .species_compatibility_threshold_drag_value,
+            species_disjoint_coefficient: control_center_ui.species_disjoint_coefficient_drag_value,
+            species_weight_coefficient: control_center_ui.species_weight_coefficient_drag_value,
             rotation_speed_max: control_center_ui.rotation_speed_max_drag_value,
             acceleration_max: control_center_ui.acceleration_max_drag_value,
+            speed_energy_drain: control_center_ui.speed_energy_drain_drag_value,
             is_paused: simulation_settings.is_paused,
         };
+        // Zufallsgenerator nur bei geändertem Seed neu seeden, damit ein Lauf ab hier reproduzierbar ist
+        // (sonst würde jede Änderung an anderen Settings den Zufall des Laufs zurücksetzen)
+        if simulation_settings.seed != old_seed {
+            **simulation_rng = ChaCha12Rng::seed_from_u64(simulation_settings.seed);
+        }
+    }
+}
+
+pub struct ApplyMutationSettings;
+
+/// Event-Handler für `ApplyMutationSettings` Event
+pub fn apply_mutation_settings(
+    mut mutation_settings: ResMut<MutationSettings>,
+    mut apply_mutation_settings_events: EventReader<ApplyMutationSettings>,
+    control_center_ui: Res<ControlCenterUi>,
+) {
+    for _ in apply_mutation_settings_events.iter() {
+        *mutation_settings = MutationSettings {
+            neuron_add_chance: control_center_ui.neuron_add_chance_drag_value,
+            neuron_remove_chance: control_center_ui.neuron_remove_chance_drag_value,
+            connection_add_chance: control_center_ui.connection_add_chance_drag_value,
+            connection_remove_chance: control_center_ui.connection_remove_chance_drag_value,
+            weight_distribution: control_center_ui.weight_distribution_combo_box,
+            weight_magnitude: control_center_ui.weight_magnitude_drag_value,
+            bias_distribution: control_center_ui.bias_distribution_combo_box,
+            bias_magnitude: control_center_ui.bias_magnitude_drag_value,
+            genome_distribution: control_center_ui.genome_distribution_combo_box,
+            genome_magnitude: control_center_ui.genome_magnitude_drag_value,
+            mutation_rate_adaptation: control_center_ui.mutation_rate_adaptation_drag_value,
+        };
     }
 }
 
 pub struct TogglePause;
 
+/// Führt genau einen Tick durch und pausiert danach.
+/// Wird von `run_on_tick` verarbeitet.
+pub struct Step;
+
+/// Führt `tick_count` Ticks durch und pausiert danach.
+/// Wird von `run_on_tick` verarbeitet.
+pub struct RunTicks {
+    pub tick_count: u64,
+}
+
 /// Event-Handler für `TogglePause` Event
 pub fn toggle_pause(
     mut simulation_settings: ResMut<SimulationSettings>,
@@ -725,7 +3155,16 @@ pub struct Save {
 pub fn save(
     mut save_events: EventReader<Save>,
     world: &World,
+    statistic_query: Query<(&Label, &StatisticData), With<Statistic>>,
+    brain_query: Query<&Brain>,
     simulation_settings: Res<SimulationSettings>,
+    simulation_rng: Res<SimulationRng>,
+    mutation_settings: Res<MutationSettings>,
+    innovation_registry: Res<InnovationRegistry>,
+    species_registry: Res<SpeciesRegistry>,
+    ancestry_log: Res<AncestryLog>,
+    environment_schedule: Res<EnvironmentSchedule>,
+    tick_count: Res<TickCount>,
 ) {
     for save_event in save_events.iter() {
         // Ordner erstellen
@@ -751,6 +3190,133 @@ pub fn save(
             serialized_simulation_settings,
         )
         .unwrap();
+
+        // Zustand des Zufallsgenerators speichern
+        let serialized_simulation_rng = scene::serialize_ron(&*simulation_rng).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/simulation_rng.ron",
+                &save_event.save_name
+            )),
+            serialized_simulation_rng,
+        )
+        .unwrap();
+
+        // Mutation-Settings speichern (als eigene Datei, damit die `simulation_settings.ron`
+        // weiterhin das gleiche Format hat wie die Settings-Datei von `die_ursuppe-headless`)
+        let serialized_mutation_settings = scene::serialize_ron(&*mutation_settings).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/mutation_settings.ron",
+                &save_event.save_name
+            )),
+            serialized_mutation_settings,
+        )
+        .unwrap();
+
+        // Innovation-Registry speichern (ohne Connections, die kein Brain mehr hat)
+        let serialized_innovation_registry =
+            scene::serialize_ron(innovation_registry.pruned(&brain_query)).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/innovation_registry.ron",
+                &save_event.save_name
+            )),
+            serialized_innovation_registry,
+        )
+        .unwrap();
+
+        // Species-Registry speichern
+        let serialized_species_registry = scene::serialize_ron(&*species_registry).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/species_registry.ron",
+                &save_event.save_name
+            )),
+            serialized_species_registry,
+        )
+        .unwrap();
+
+        // Ancestry-Log speichern und als Newick und JSON exportieren
+        let serialized_ancestry_log = scene::serialize_ron(&*ancestry_log).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/ancestry_log.ron",
+                &save_event.save_name
+            )),
+            serialized_ancestry_log,
+        )
+        .unwrap();
+        fs::write(
+            Path::new(&format!("assets/{}/ancestry.nwk", &save_event.save_name)),
+            ancestry_log.to_newick(),
+        )
+        .unwrap();
+        fs::write(
+            Path::new(&format!("assets/{}/ancestry.json", &save_event.save_name)),
+            ancestry_log.to_json(),
+        )
+        .unwrap();
+
+        // Environment-Schedule speichern
+        let serialized_environment_schedule = scene::serialize_ron(&*environment_schedule).unwrap();
+        fs::write(
+            Path::new(&format!(
+                "assets/{}/environment_schedule.ron",
+                &save_event.save_name
+            )),
+            serialized_environment_schedule,
+        )
+        .unwrap();
+
+        // Tick-Count speichern
+        let serialized_tick_count = scene::serialize_ron(&*tick_count).unwrap();
+        fs::write(
+            Path::new(&format!("assets/{}/tick_count.ron", &save_event.save_name)),
+            serialized_tick_count,
+        )
+        .unwrap();
+
+        // Statistiken als CSV speichern (eine Datei pro Statistik, eine Spalte pro Linie)
+        fs::create_dir_all(Path::new(&format!(
+            "assets/{}/statistics",
+            &save_event.save_name
+        )))
+        .unwrap();
+        for (label, statistic_data) in &statistic_query {
+            let mut csv = "index".to_string();
+            for line in &statistic_data.lines {
+                write!(csv, ",{}", line.legend_name).unwrap();
+            }
+            let row_count = statistic_data
+                .lines
+                .iter()
+                .map(|line| line.start_index + line.data_points.len())
+                .max()
+                .unwrap_or(0);
+            for row in 0..row_count {
+                write!(csv, "\n{}", row).unwrap();
+                for line in &statistic_data.lines {
+                    csv.push(',');
+                    if let Some(data_point) = row
+                        .checked_sub(line.start_index)
+                        .and_then(|index| line.data_points.get(index))
+                    {
+                        write!(csv, "{}", data_point).unwrap();
+                    }
+                }
+            }
+            csv.push('\n');
+            fs::write(
+                Path::new(&format!(
+                    "assets/{}/statistics/{}.csv",
+                    &save_event.save_name,
+                    label.to_lowercase().replace(' ', "_")
+                )),
+                csv,
+            )
+            .unwrap();
+        }
     }
 }
 
@@ -763,6 +3329,13 @@ pub fn load(
     mut commands: Commands,
     mut load_events: EventReader<Load>,
     mut simulation_settings: ResMut<SimulationSettings>,
+    mut simulation_rng: ResMut<SimulationRng>,
+    mut mutation_settings: ResMut<MutationSettings>,
+    mut innovation_registry: ResMut<InnovationRegistry>,
+    mut species_registry: ResMut<SpeciesRegistry>,
+    mut ancestry_log: ResMut<AncestryLog>,
+    mut environment_schedule: ResMut<EnvironmentSchedule>,
+    mut tick_count: ResMut<TickCount>,
     entity_query: Query<Entity>,
     asset_server: Res<AssetServer>,
 ) {
@@ -790,5 +3363,66 @@ pub fn load(
         *simulation_settings = ron::from_str(&serialized_simulation_settings).unwrap();
         // Simulation pausieren um zu verhindern, dass in diesem Frame noch ein Tick durchgeführt wird
         simulation_settings.is_paused = true;
+
+        // Zustand des Zufallsgenerators laden
+        // (ältere Saves haben noch keinen, dann wird neu aus dem Seed gezogen und der Lauf ist ein neuer Zweig)
+        *simulation_rng = fs::read_to_string(Path::new(&format!(
+            "assets/{}/simulation_rng.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_simulation_rng| ron::from_str(&serialized_simulation_rng).unwrap())
+        .unwrap_or_else(|_| SimulationRng(ChaCha12Rng::seed_from_u64(simulation_settings.seed)));
+
+        // Mutation-Settings laden (ältere Saves haben noch keine)
+        *mutation_settings = fs::read_to_string(Path::new(&format!(
+            "assets/{}/mutation_settings.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_mutation_settings| ron::from_str(&serialized_mutation_settings).unwrap())
+        .unwrap_or_default();
+
+        // Innovation-Registry laden (ältere Saves haben noch keine)
+        *innovation_registry = fs::read_to_string(Path::new(&format!(
+            "assets/{}/innovation_registry.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_innovation_registry| {
+            ron::from_str(&serialized_innovation_registry).unwrap()
+        })
+        .unwrap_or_default();
+
+        // Species-Registry laden (ältere Saves haben noch keine)
+        *species_registry = fs::read_to_string(Path::new(&format!(
+            "assets/{}/species_registry.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_species_registry| ron::from_str(&serialized_species_registry).unwrap())
+        .unwrap_or_default();
+
+        // Ancestry-Log laden (ältere Saves haben noch keins)
+        *ancestry_log = fs::read_to_string(Path::new(&format!(
+            "assets/{}/ancestry_log.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_ancestry_log| ron::from_str(&serialized_ancestry_log).unwrap())
+        .unwrap_or_default();
+
+        // Environment-Schedule laden (ältere Saves haben noch keinen)
+        *environment_schedule = fs::read_to_string(Path::new(&format!(
+            "assets/{}/environment_schedule.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_environment_schedule| {
+            ron::from_str(&serialized_environment_schedule).unwrap()
+        })
+        .unwrap_or_default();
+
+        // Tick-Count laden (ältere Saves haben noch keinen)
+        *tick_count = fs::read_to_string(Path::new(&format!(
+            "assets/{}/tick_count.ron",
+            &load_event.save_name
+        )))
+        .map(|serialized_tick_count| ron::from_str(&serialized_tick_count).unwrap())
+        .unwrap_or_default();
     }
 }
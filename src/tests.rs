@@ -1,48 +1,2046 @@
 #[test]
 fn brain_new_immune_neuron_count_test() {
-    use crate::brain::{Brain, IMMUNE_NEURON_COUNT};
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
 
     // Neues Brain erstellen
-    let brain = Brain::new();
+    let sense_registry = SenseRegistry::default();
+    let brain = Brain::new(&sense_registry);
 
     // Garantieren, dass ein neues Brain die richtige Anzahl an Neuronen hat
-    assert_eq!(brain.neurons().len() as u8, IMMUNE_NEURON_COUNT);
+    assert_eq!(brain.neurons().len(), sense_registry.neuron_count());
 }
 
 #[test]
 fn brain_mutate_immune_neuron_count_test() {
-    use crate::brain::{Brain, IMMUNE_NEURON_COUNT};
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
 
     // Neues Brain erstellen
-    let mut brain = Brain::new();
+    let sense_registry = SenseRegistry::default();
+    let mut brain = Brain::new(&sense_registry);
 
     // Brain 10000 mal mutieren lassen
+    let mutation_settings = MutationSettings::default();
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
     for _ in 0..10000 {
-        brain.mutate();
+        brain.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
 
         // Garantieren, dass das Brain nie weniger als die Mindestanzahl an Neuronen hat
-        assert!(brain.neurons().len() >= IMMUNE_NEURON_COUNT as usize);
+        assert!(brain.neurons().len() >= sense_registry.neuron_count());
     }
 }
 
 #[test]
 fn brain_read_neuron_write_neuron_test() {
-    use crate::brain::{Brain, IMMUNE_NEURON_COUNT};
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
 
     // Neues Brain erstellen
-    let mut brain = Brain::new();
+    let sense_registry = SenseRegistry::default();
+    let mut brain = Brain::new(&sense_registry);
 
-    if IMMUNE_NEURON_COUNT > 0 {
+    if sense_registry.neuron_count() > 0 {
         // Garantieren, dass es das Neuron 0 gibt und es mit dem Standartwert 0 initialisiert wurde
         assert_eq!(brain.read_neuron(0), Some(0.));
     }
 
     // Garantieren, dass read_neuron None zurück gibt, falls das gewünschte Neuron nicht existiert
-    assert_eq!(brain.read_neuron(IMMUNE_NEURON_COUNT as usize), None);
+    assert_eq!(brain.read_neuron(sense_registry.neuron_count()), None);
 
-    if IMMUNE_NEURON_COUNT > 0 {
+    if sense_registry.neuron_count() > 0 {
         brain.write_neuron(0, 5.);
         // Garantieren, dass das gerade beschriebene Neuron 0 jetzt den neuen Wert enthält
         assert_eq!(brain.read_neuron(0), Some(5.));
     }
 }
+
+#[test]
+fn brain_mutate_seed_determinism_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // Zwei Brains mit dem gleichen Seed mutieren lassen
+    let sense_registry = SenseRegistry::default();
+    let mutation_settings = MutationSettings::default();
+    let mut brain_a = Brain::new(&sense_registry);
+    let mut brain_b = Brain::new(&sense_registry);
+    let mut innovation_registry_a = InnovationRegistry::default();
+    let mut innovation_registry_b = InnovationRegistry::default();
+    let mut rng_a = StdRng::seed_from_u64(42);
+    let mut rng_b = StdRng::seed_from_u64(42);
+    for _ in 0..1000 {
+        brain_a.mutate(&mutation_settings, &mut innovation_registry_a, &mut rng_a);
+        brain_b.mutate(&mutation_settings, &mut innovation_registry_b, &mut rng_b);
+    }
+
+    // Garantieren, dass beide Brains exakt gleich sind
+    assert_eq!(format!("{:?}", brain_a), format!("{:?}", brain_b));
+}
+
+/// Save im Format von vor den Neuron-Ids, der [InnovationRegistry](crate::brain::InnovationRegistry) und den [Species](crate::sim::Species):
+/// Ein Chunk und eine Zelle, deren Brain ein verstecktes Neuron (Index 8) zwischen "Nearest food angle" (0) und "Rotation" (5) hat
+#[cfg(test)]
+const BASELINE_SCENE: &str = r#"(
+  entities: {
+    0: (
+      components: {
+        "die_ursuppe::sim::ChunkRegistry": (
+          chunk_size: 200.0,
+          map_size: 1,
+          entries: [[(generation: 0, index: 1)]],
+        ),
+      },
+    ),
+    1: (
+      components: {
+        "die_ursuppe::sim::Chunk": (),
+        "die_ursuppe::sim::Position": (x: 100.0, y: 100.0),
+        "die_ursuppe::sim::Foodlist": ([]),
+        "die_ursuppe::sim::ChunkSettings": (
+          spawn_chance: 0.0,
+          spawned_food_energy: 200.0,
+          velocity_damping: 0.4,
+        ),
+      },
+    ),
+    2: (
+      components: {
+        "die_ursuppe::sim::Cell": (),
+        "die_ursuppe::sim::Position": (x: 100.0, y: 100.0),
+        "die_ursuppe::sim::Rotation": (0.0),
+        "die_ursuppe::sim::Velocity": (x: 0.0, y: 0.0),
+        "die_ursuppe::sim::Energy": (50.0),
+        "die_ursuppe::sim::ChildCooldown": (0),
+        "die_ursuppe::sim::CellStats": (age: 3, child_count: 0),
+        "die_ursuppe::brain::Brain": (
+          neurons: [
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [(neuron_index: 8, weight: 2.0)], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.0, output: 0.0),
+            (inputs: [], bias: 0.5, output: 0.0),
+            (inputs: [(neuron_index: 0, weight: 1.5)], bias: 0.1, output: 0.0),
+          ],
+        ),
+      },
+    ),
+  },
+)"#;
+
+/// Erstellt die Simulation wie `die_ursuppe-headless` und lädt [BASELINE_SCENE] an Stelle der Chunks
+/// (wie `load` bei einem alten Save)
+#[cfg(test)]
+fn load_baseline_scene() -> bevy::prelude::App {
+    use crate::sim::{Chunk, ChunkRegistry};
+    use crate::SimulationPlugin;
+    use bevy::{ecs::entity::EntityMap, prelude::*, scene::serde::SceneDeserializer};
+    use serde::de::DeserializeSeed;
+
+    let mut app = App::new();
+    // `Vec<Entity>` wird sonst von den `DefaultPlugins` registriert
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(SimulationPlugin)
+        .register_type::<Vec<Entity>>();
+    app.update();
+    let chunk_entities: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, Or<(With<Chunk>, With<ChunkRegistry>)>>()
+        .iter(&app.world)
+        .collect();
+    for entity in chunk_entities {
+        app.world.despawn(entity);
+    }
+    let type_registry = app.world.resource::<AppTypeRegistry>().clone();
+    let scene = SceneDeserializer {
+        type_registry: &type_registry.read(),
+    }
+    .deserialize(&mut ron::de::Deserializer::from_str(BASELINE_SCENE).unwrap())
+    .unwrap();
+    scene
+        .write_to_world(&mut app.world, &mut EntityMap::default())
+        .unwrap();
+    app.update();
+    app
+}
+
+/// Erstellt eine Welt mit allen Ressourcen, Chunks und Statistiken, die `tick_cells` braucht
+/// (die [SenseRegistry](crate::senses::SenseRegistry) passt zu `signal_channel_count`)
+#[cfg(test)]
+fn tick_world(simulation_settings: crate::sim::SimulationSettings) -> bevy::prelude::World {
+    use crate::brain::{InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+    use crate::sim::{setup_chunks, AncestryLog, RemainingEnergies, SimulationRng, TickCount};
+    use crate::ui::setup_statistics;
+    use bevy::prelude::*;
+
+    let mut world = World::new();
+    world.insert_resource(SenseRegistry::with_signal_channels(
+        simulation_settings.signal_channel_count,
+    ));
+    world.insert_resource(simulation_settings);
+    world.insert_resource(MutationSettings::default());
+    world.insert_resource(InnovationRegistry::default());
+    world.insert_resource(AncestryLog::default());
+    world.insert_resource(SimulationRng::default());
+    world.insert_resource(TickCount::default());
+    world.insert_resource(RemainingEnergies::default());
+    SystemStage::single(setup_chunks).run(&mut world);
+    SystemStage::single(setup_statistics).run(&mut world);
+    world
+}
+
+/// Index des Output-Neurons des Actuators `actuator_name`
+#[cfg(test)]
+fn actuator_neuron_index(
+    sense_registry: &crate::senses::SenseRegistry,
+    actuator_name: &str,
+) -> usize {
+    sense_registry.actuator_neuron_index(
+        sense_registry
+            .actuators()
+            .iter()
+            .position(|actuator| actuator.name == actuator_name)
+            .unwrap(),
+    )
+}
+
+/// Index des Input-Neurons des Sensors `sensor_name`
+#[cfg(test)]
+fn sensor_neuron_index(sense_registry: &crate::senses::SenseRegistry, sensor_name: &str) -> usize {
+    sense_registry.sensor_neuron_index(
+        sense_registry
+            .sensors()
+            .iter()
+            .position(|sensor| sensor.name == sensor_name)
+            .unwrap(),
+    )
+}
+
+/// Verbindet im `brain` den Sensor `sensor_name` mit dem Gewicht `weight` mit dem Actuator `actuator_name`
+#[cfg(test)]
+fn connect(
+    brain: &mut crate::brain::Brain,
+    sense_registry: &crate::senses::SenseRegistry,
+    sensor_name: &str,
+    actuator_name: &str,
+    weight: f32,
+) {
+    use crate::brain::NeuronInput;
+    use bevy::reflect::GetPath;
+
+    // Jede Connection bekommt eine eigene Innovationsnummer
+    let innovation = brain
+        .neurons()
+        .iter()
+        .map(|neuron| neuron.inputs.len() as u64)
+        .sum();
+    brain
+        .get_path_mut::<Vec<NeuronInput>>(&format!(
+            "neurons[{}].inputs",
+            actuator_neuron_index(sense_registry, actuator_name)
+        ))
+        .unwrap()
+        .push(NeuronInput {
+            neuron_index: sensor_neuron_index(sense_registry, sensor_name),
+            innovation,
+            weight,
+        });
+}
+
+#[test]
+fn baseline_scene_load_test() {
+    use crate::brain::{Brain, InnovationRegistry, FIRST_HIDDEN_NEURON_ID};
+    use crate::senses::SenseRegistry;
+
+    // Alten Save laden
+    let mut app = load_baseline_scene();
+    let brain = app.world.query::<&Brain>().single(&app.world).clone();
+
+    // Garantieren, dass das versteckte Neuron seine Id aus dem Index bekommt und erhalten bleibt
+    let sense_registry = app.world.resource::<SenseRegistry>();
+    assert_eq!(brain.neurons().len(), sense_registry.neuron_count() + 1);
+    let hidden_neuron = brain
+        .neurons()
+        .iter()
+        .find(|neuron| neuron.id >= FIRST_HIDDEN_NEURON_ID)
+        .unwrap();
+    assert_eq!(hidden_neuron.id, FIRST_HIDDEN_NEURON_ID + 8);
+    assert_eq!(hidden_neuron.bias, 0.1);
+
+    // Garantieren, dass beide Connections verschiedene Innovationsnummern aus der Registry bekommen
+    let innovations: Vec<u64> = brain
+        .neurons()
+        .iter()
+        .flat_map(|neuron| neuron.inputs.iter().map(|input| input.innovation))
+        .collect();
+    assert_eq!(innovations.len(), 2);
+    assert_ne!(innovations[0], innovations[1]);
+    let mut innovation_registry = app.world.resource_mut::<InnovationRegistry>();
+    assert_eq!(innovation_registry.connection_count(), 2);
+    assert!(innovations.iter().all(|innovation| *innovation < 2));
+
+    // Garantieren, dass die Id des versteckten Neurons nicht erneut vergeben wird
+    assert!(innovation_registry.new_neuron_id() > FIRST_HIDDEN_NEURON_ID + 8);
+}
+
+#[test]
+fn baseline_scene_missing_components_test() {
+    use crate::genome::Genome;
+    use crate::sim::{
+        AncestryLog, Cell, CellStats, Lineage, RunTicks, Signals, Species, TickCount,
+    };
+    use bevy::prelude::*;
+
+    // Alten Save laden
+    let mut app = load_baseline_scene();
+    app.update();
+
+    // Garantieren, dass die Zelle alle Komponenten bekommt, die `tick_cells` braucht
+    let cell_entity = app
+        .world
+        .query_filtered::<Entity, With<Cell>>()
+        .single(&app.world);
+    assert!(app.world.get::<Genome>(cell_entity).is_some());
+    assert!(app.world.get::<Signals>(cell_entity).is_some());
+    assert!(app.world.get::<Species>(cell_entity).is_some());
+
+    // Garantieren, dass die Zelle als Anfang einer neuen Abstammungslinie im Ancestry-Log steht
+    let lineage = *app.world.get::<Lineage>(cell_entity).unwrap();
+    assert_ne!(lineage.id, 0);
+    assert_eq!(lineage.parent_id, None);
+    assert_eq!(app.world.resource::<AncestryLog>().entries.len(), 1);
+    assert!(app
+        .world
+        .resource::<AncestryLog>()
+        .to_newick()
+        .contains(&lineage.id.to_string()));
+
+    // Garantieren, dass die Zelle danach an den Ticks teilnimmt
+    app.world
+        .resource_mut::<Events<RunTicks>>()
+        .send(RunTicks { tick_count: 5 });
+    while **app.world.resource::<TickCount>() < 5 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<CellStats>(cell_entity).unwrap().age, 3 + 5);
+}
+
+#[test]
+fn simulation_seed_determinism_test() {
+    use crate::sim::{
+        Cell, Position, RunTicks, SimulationRng, SimulationSettings, SpawnCell, TickCount,
+    };
+    use crate::SimulationPlugin;
+    use bevy::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    // Die ganze Simulation mit festem Seed 100 Ticks lang laufen lassen (wie `die_ursuppe-headless`)
+    let run_simulation = || {
+        let simulation_settings = SimulationSettings {
+            seed: 42,
+            tick_delta_seconds: 0.,
+            is_paused: true,
+            ..default()
+        };
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(SimulationRng(ChaCha12Rng::seed_from_u64(
+                simulation_settings.seed,
+            )))
+            .insert_resource(simulation_settings)
+            .add_plugin(SimulationPlugin);
+        app.update();
+        for _ in 0..50 {
+            app.world
+                .resource_mut::<Events<SpawnCell>>()
+                .send(SpawnCell { energy: 199. });
+        }
+        app.world
+            .resource_mut::<Events<RunTicks>>()
+            .send(RunTicks { tick_count: 100 });
+        while **app.world.resource::<TickCount>() < 100 {
+            app.update();
+        }
+        app.world
+            .query_filtered::<&Position, With<Cell>>()
+            .iter(&app.world)
+            .map(|position| (position.x, position.y))
+            .collect::<Vec<(f32, f32)>>()
+    };
+
+    // Garantieren, dass zwei Läufe mit dem gleichen Seed die gleichen Zellen an den gleichen Positionen haben
+    let cells_a = run_simulation();
+    let cells_b = run_simulation();
+    assert!(!cells_a.is_empty());
+    assert_eq!(cells_a, cells_b);
+}
+
+#[test]
+fn simulation_rng_save_load_test() {
+    use crate::sim::SimulationRng;
+    use bevy::scene;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    // Zufallsgenerator ein Stück laufen lassen und wie `save` serialisieren
+    let mut simulation_rng = SimulationRng(ChaCha12Rng::seed_from_u64(42));
+    for _ in 0..100 {
+        simulation_rng.gen::<f32>();
+    }
+    let serialized_simulation_rng = scene::serialize_ron(&simulation_rng).unwrap();
+
+    // Wie `load` deserialisieren
+    let mut loaded_simulation_rng: SimulationRng =
+        ron::from_str(&serialized_simulation_rng).unwrap();
+
+    // Garantieren, dass der geladene Zufallsgenerator dort weitermacht, wo gespeichert wurde,
+    // und nicht wieder beim Seed anfängt
+    let continued = (0..100)
+        .map(|_| simulation_rng.gen::<u64>())
+        .collect::<Vec<u64>>();
+    let loaded = (0..100)
+        .map(|_| loaded_simulation_rng.gen::<u64>())
+        .collect::<Vec<u64>>();
+    let mut reseeded_rng = ChaCha12Rng::seed_from_u64(42);
+    let reseeded = (0..100)
+        .map(|_| reseeded_rng.gen::<u64>())
+        .collect::<Vec<u64>>();
+    assert_eq!(loaded, continued);
+    assert_ne!(loaded, reseeded);
+}
+
+#[test]
+fn brain_mutate_without_mutation_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+
+    // Mutation-Settings, bei denen sich nichts verändern darf
+    let mutation_settings = MutationSettings {
+        neuron_add_chance: 0.,
+        neuron_remove_chance: 0.,
+        connection_add_chance: 0.,
+        connection_remove_chance: 0.,
+        weight_magnitude: 0.,
+        bias_magnitude: 0.,
+        ..Default::default()
+    };
+
+    // Brain mit ein paar Neuronen und Connections erstellen
+    let sense_registry = SenseRegistry::default();
+    let mut brain = Brain::new(&sense_registry);
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        brain.mutate(
+            &MutationSettings::default(),
+            &mut innovation_registry,
+            &mut rng,
+        );
+    }
+    let brain_before = format!("{:?}", brain);
+
+    // Garantieren, dass das Brain ohne Mutations-Wahrscheinlichkeiten unverändert bleibt
+    for _ in 0..100 {
+        brain.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+    }
+    assert_eq!(format!("{:?}", brain), brain_before);
+}
+
+#[test]
+fn brain_crossover_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+
+    // Zwei unterschiedliche Brains mit gemeinsamem Vorfahren erstellen
+    let sense_registry = SenseRegistry::default();
+    let mutation_settings = MutationSettings::default();
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
+    let mut brain_a = Brain::new(&sense_registry);
+    for _ in 0..100 {
+        brain_a.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+    }
+    let mut brain_b = brain_a.clone();
+    for _ in 0..100 {
+        brain_a.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+        brain_b.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+    }
+
+    for _ in 0..100 {
+        let child = brain_a.crossover(&brain_b, &mut rng);
+
+        // Garantieren, dass das Kind den Aufbau des ersten Elternteils übernimmt
+        assert_eq!(child.neurons().len(), brain_a.neurons().len());
+        assert!(child.neurons().len() >= sense_registry.neuron_count());
+        for (child_neuron, neuron_a) in child.neurons().iter().zip(brain_a.neurons()) {
+            assert_eq!(child_neuron.id, neuron_a.id);
+            let child_innovations: Vec<u64> = child_neuron
+                .inputs
+                .iter()
+                .map(|input| input.innovation)
+                .collect();
+            let innovations_a: Vec<u64> = neuron_a
+                .inputs
+                .iter()
+                .map(|input| input.innovation)
+                .collect();
+            assert_eq!(child_innovations, innovations_a);
+        }
+
+        // Garantieren, dass alle Connections auf existierende Neuronen zeigen
+        for neuron in child.neurons() {
+            for input in &neuron.inputs {
+                assert!(input.neuron_index < child.neurons().len());
+            }
+        }
+    }
+}
+
+#[test]
+fn brain_innovation_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+    use std::collections::{HashMap, HashSet};
+
+    // Mehrere Brains mit der gleichen Innovation-Registry mutieren lassen
+    let sense_registry = SenseRegistry::default();
+    let mutation_settings = MutationSettings::default();
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
+    let mut brains = vec![Brain::new(&sense_registry); 10];
+    for _ in 0..500 {
+        for brain in &mut brains {
+            brain.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+        }
+    }
+
+    let mut connections_by_innovation = HashMap::new();
+    for brain in &brains {
+        // Garantieren, dass die immunen Neuronen an ihrem Platz aus der SenseRegistry bleiben
+        assert!(brain
+            .neurons()
+            .iter()
+            .map(|neuron| neuron.id)
+            .take(sense_registry.neuron_count())
+            .eq(sense_registry.neuron_ids()));
+
+        // Garantieren, dass jede Id nur einmal pro Brain vorkommt
+        let neuron_ids: HashSet<u32> = brain.neurons().iter().map(|neuron| neuron.id).collect();
+        assert_eq!(neuron_ids.len(), brain.neurons().len());
+
+        // Garantieren, dass eine Innovationsnummer in allen Brains die gleiche Connection bezeichnet
+        for neuron in brain.neurons() {
+            for input in &neuron.inputs {
+                let connection = (brain.neurons()[input.neuron_index].id, neuron.id);
+                assert_eq!(
+                    *connections_by_innovation
+                        .entry(input.innovation)
+                        .or_insert(connection),
+                    connection
+                );
+            }
+        }
+    }
+
+    // Garantieren, dass beim Aufräumen nur die Innovationsnummern der übrigen Brains erhalten bleiben
+    let mut pruned_innovation_registry = innovation_registry.pruned(&brains[..5]);
+    let used_innovations: HashSet<u64> = brains[..5]
+        .iter()
+        .flat_map(|brain| brain.neurons().iter())
+        .flat_map(|neuron| neuron.inputs.iter().map(|input| input.innovation))
+        .collect();
+    assert_eq!(
+        pruned_innovation_registry.connection_count(),
+        used_innovations.len()
+    );
+    assert!(pruned_innovation_registry.connection_count() < innovation_registry.connection_count());
+    for (innovation, (from_neuron_id, to_neuron_id)) in &connections_by_innovation {
+        let pruned_innovation =
+            pruned_innovation_registry.connection_innovation(*from_neuron_id, *to_neuron_id);
+        if used_innovations.contains(innovation) {
+            assert_eq!(pruned_innovation, *innovation);
+        } else {
+            // Entfernte Connections bekommen eine neue, noch nie vergebene Innovationsnummer
+            assert!(!connections_by_innovation.contains_key(&pruned_innovation));
+        }
+    }
+}
+
+#[test]
+fn brain_compatibility_distance_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+
+    // Zwei Brains mit gemeinsamem Vorfahren erstellen
+    let sense_registry = SenseRegistry::default();
+    let mutation_settings = MutationSettings::default();
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
+    let mut brain_a = Brain::new(&sense_registry);
+    for _ in 0..100 {
+        brain_a.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+    }
+    let mut brain_b = brain_a.clone();
+
+    // Garantieren, dass gleiche Brains die Distanz 0 haben
+    assert_eq!(brain_a.compatibility_distance(&brain_b, 1., 0.4), 0.);
+
+    // Garantieren, dass sich die Distanz erhöht, sobald ein Brain neue Neuronen hat
+    let structure_mutation_settings = MutationSettings {
+        neuron_add_chance: 1.,
+        neuron_remove_chance: 0.,
+        connection_add_chance: 0.,
+        connection_remove_chance: 0.,
+        weight_magnitude: 0.,
+        bias_magnitude: 0.,
+        ..Default::default()
+    };
+    brain_b.mutate(
+        &structure_mutation_settings,
+        &mut innovation_registry,
+        &mut rng,
+    );
+    let distance = brain_a.compatibility_distance(&brain_b, 1., 0.4);
+    assert!(distance > 0.);
+
+    // Garantieren, dass die Distanz symmetrisch ist
+    assert_eq!(brain_b.compatibility_distance(&brain_a, 1., 0.4), distance);
+}
+
+#[test]
+fn ancestry_log_newick_test() {
+    use crate::sim::AncestryLog;
+
+    // Zwei Abstammungslinien erstellen: 1 -> 3 -> 4 und 2
+    let mut ancestry_log = AncestryLog::default();
+    let lineage_1 = ancestry_log.record_birth(None, None, 0);
+    let lineage_2 = ancestry_log.record_birth(None, None, 0);
+    let lineage_3 = ancestry_log.record_birth(Some(&lineage_1), None, 5);
+    let lineage_4 = ancestry_log.record_birth(Some(&lineage_3), Some(&lineage_2), 7);
+    ancestry_log.record_death(&lineage_1, 9);
+
+    // Garantieren, dass Ids, Eltern und Generationen richtig vergeben werden
+    assert_eq!(lineage_4.id, 4);
+    assert_eq!(lineage_4.parent_id, Some(3));
+    assert_eq!(lineage_4.generation, 2);
+    assert_eq!(ancestry_log.entries[3].mate_id, Some(2));
+    assert_eq!(ancestry_log.entries[0].death_tick, Some(9));
+    assert_eq!(ancestry_log.entries[1].death_tick, None);
+
+    // Garantieren, dass der Stammbaum richtig als Newick exportiert wird
+    assert_eq!(ancestry_log.to_newick(), "(((4:2)3:5)1:0,2:0);");
+}
+
+#[test]
+fn chunk_registry_chunks_in_radius_test() {
+    use crate::sim::{ChunkRegistry, Position, WorldTopology};
+    use bevy::prelude::Entity;
+
+    // Chunk-Registry mit 4x4 Chunks der Größe 10 erstellen (Entity-Id = index * 4 + idy)
+    let chunk_registry = ChunkRegistry {
+        chunk_size: 10.,
+        map_size: 4,
+        map_height: 4,
+        entries: (0..4)
+            .map(|index| {
+                (0..4)
+                    .map(|idy| Entity::from_raw(index * 4 + idy))
+                    .collect()
+            })
+            .collect(),
+    };
+    let position = Position { x: 15., y: 15. };
+
+    // Garantieren, dass ein kleiner Radius nur den eigenen Chunk findet
+    assert_eq!(
+        chunk_registry.chunks_in_radius(&position, 3., WorldTopology::BoundedLethal),
+        vec![Entity::from_raw(5)]
+    );
+
+    // Garantieren, dass die Ecken erst gefunden werden, wenn der Radius sie auch erreicht
+    assert_eq!(
+        chunk_registry
+            .chunks_in_radius(&position, 6., WorldTopology::BoundedLethal)
+            .len(),
+        5
+    );
+    assert_eq!(
+        chunk_registry
+            .chunks_in_radius(&position, 8., WorldTopology::BoundedLethal)
+            .len(),
+        9
+    );
+
+    // Garantieren, dass sehr große Radien über mehrere Chunks hinweg funktionieren
+    assert_eq!(
+        chunk_registry
+            .chunks_in_radius(&position, 100., WorldTopology::BoundedLethal)
+            .len(),
+        16
+    );
+
+    // Garantieren, dass Chunks außerhalb der Map ignoriert werden
+    assert_eq!(
+        chunk_registry.chunks_in_radius(
+            &Position { x: 1., y: 1. },
+            5.,
+            WorldTopology::BoundedLethal
+        ),
+        vec![Entity::from_raw(0)]
+    );
+
+    // Garantieren, dass auf einem Torus über den Rand hinweg gesucht wird (ohne Chunks doppelt zu finden)
+    let mut torus_chunks =
+        chunk_registry.chunks_in_radius(&Position { x: 1., y: 1. }, 5., WorldTopology::Torus);
+    torus_chunks.sort();
+    assert_eq!(
+        torus_chunks,
+        vec![
+            Entity::from_raw(0),
+            Entity::from_raw(3),
+            Entity::from_raw(12),
+            Entity::from_raw(15)
+        ]
+    );
+    assert_eq!(
+        chunk_registry
+            .chunks_in_radius(&position, 100., WorldTopology::Torus)
+            .len(),
+        16
+    );
+
+    // Garantieren, dass Distanzen auf einem Torus den kürzesten Weg über den Rand nehmen
+    let relative_position = chunk_registry.relative_position(
+        &Position { x: 1., y: 20. },
+        &Position { x: 39., y: 20. },
+        WorldTopology::Torus,
+    );
+    assert!((relative_position.x + 2.).abs() < 1e-5 && relative_position.y.abs() < 1e-5);
+}
+
+#[test]
+fn ray_circle_distance_test() {
+    use crate::sim::{ray_circle_distance, Position};
+
+    let origin = Position { x: 0., y: 0. };
+
+    // Garantieren, dass ein Kreis direkt vor dem Strahl an seinem Rand getroffen wird
+    let distance = ray_circle_distance(&origin, (1., 0.), &Position { x: 10., y: 0. }, 2.);
+    assert!((distance.unwrap() - 8.).abs() < 1e-5);
+
+    // Garantieren, dass Kreise hinter dem Strahl und seitlich daneben nicht getroffen werden
+    assert_eq!(
+        ray_circle_distance(&origin, (1., 0.), &Position { x: -10., y: 0. }, 2.),
+        None
+    );
+    assert_eq!(
+        ray_circle_distance(&origin, (1., 0.), &Position { x: 10., y: 3. }, 2.),
+        None
+    );
+
+    // Garantieren, dass die Distanz 0 ist, wenn der Ursprung im Kreis liegt
+    assert_eq!(
+        ray_circle_distance(&origin, (0., 1.), &Position { x: 1., y: 0. }, 2.),
+        Some(0.)
+    );
+}
+
+#[test]
+fn brain_adapt_to_senses_test() {
+    use crate::brain::{Brain, InnovationRegistry, MutationSettings};
+    use crate::senses::SenseRegistry;
+    use std::collections::HashSet;
+
+    // Brain mit kleiner SenseRegistry erstellen und mutieren lassen
+    let mut old_sense_registry = SenseRegistry::new();
+    old_sense_registry.register_sensor("Energy", 3, |perception| perception.energy);
+    old_sense_registry.register_actuator("Rotation", 5, |actions, value| actions.rotation = value);
+    let mutation_settings = MutationSettings::default();
+    let mut innovation_registry = InnovationRegistry::default();
+    let mut rng = rand::thread_rng();
+    let mut brain = Brain::new(&old_sense_registry);
+    for _ in 0..200 {
+        brain.mutate(&mutation_settings, &mut innovation_registry, &mut rng);
+    }
+    let connections = |brain: &Brain| -> HashSet<(u32, u32, u64)> {
+        brain
+            .neurons()
+            .iter()
+            .flat_map(|neuron| {
+                neuron.inputs.iter().map(|input| {
+                    (
+                        brain.neurons()[input.neuron_index].id,
+                        neuron.id,
+                        input.innovation,
+                    )
+                })
+            })
+            .collect()
+    };
+    let old_connections = connections(&brain);
+    let old_neuron_count = brain.neurons().len();
+
+    // An die Standard-SenseRegistry anpassen
+    let sense_registry = SenseRegistry::default();
+    brain.adapt_to_senses(&sense_registry);
+
+    // Garantieren, dass die Input- und Output-Neuronen in der Reihenfolge der SenseRegistry am Anfang liegen
+    assert!(brain
+        .neurons()
+        .iter()
+        .map(|neuron| neuron.id)
+        .take(sense_registry.neuron_count())
+        .eq(sense_registry.neuron_ids()));
+
+    // Garantieren, dass nur die fehlenden Neuronen hinzugekommen sind und alle Connections erhalten bleiben
+    assert_eq!(
+        brain.neurons().len(),
+        old_neuron_count + sense_registry.neuron_count() - 2
+    );
+    assert_eq!(connections(&brain), old_connections);
+}
+
+#[test]
+fn brain_adapt_baseline_layout_test() {
+    use crate::brain::{Brain, FIRST_HIDDEN_NEURON_ID};
+    use crate::senses::SenseRegistry;
+
+    // Alten Save laden (das Brain wird beim Laden an die SenseRegistry angepasst)
+    let mut app = load_baseline_scene();
+    let mut brain = app.world.query::<&Brain>().single(&app.world).clone();
+    let sense_registry = app.world.resource::<SenseRegistry>();
+
+    // Garantieren, dass das versteckte Neuron weiterhin von "Nearest food angle" zu "Rotation" verbunden ist
+    let rotation_inputs =
+        &brain.neurons()[actuator_neuron_index(sense_registry, "Rotation")].inputs;
+    assert_eq!(rotation_inputs.len(), 1);
+    assert_eq!(rotation_inputs[0].weight, 2.);
+    let hidden_neuron = &brain.neurons()[rotation_inputs[0].neuron_index];
+    assert_eq!(hidden_neuron.id, FIRST_HIDDEN_NEURON_ID + 8);
+    assert_eq!(hidden_neuron.inputs.len(), 1);
+    assert_eq!(
+        hidden_neuron.inputs[0].neuron_index,
+        sensor_neuron_index(sense_registry, "Nearest food angle")
+    );
+    assert_eq!(hidden_neuron.inputs[0].weight, 1.5);
+
+    // Garantieren, dass das alte Output-Neuron 7 weiterhin den Kinderwunsch steuert
+    brain.tick();
+    assert_eq!(
+        sense_registry.read_actuators(&brain).want_child,
+        0.5_f32.tanh()
+    );
+}
+
+#[test]
+fn collide_cells_test() {
+    use crate::genome::Genome;
+    use crate::sim::{collide_cells, Cell, ChunkRegistry, Position, SimulationSettings, Velocity};
+    use bevy::prelude::*;
+
+    // Welt mit einem einzigen Chunk und zwei überlappenden Zellen erstellen, die aufeinander zu fliegen
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings {
+        cell_radius: 5.,
+        cell_collision_elasticity: 1.,
+        ..Default::default()
+    });
+    world.spawn(ChunkRegistry {
+        chunk_size: 100.,
+        map_size: 1,
+        map_height: 1,
+        entries: vec![vec![Entity::from_raw(1000)]],
+    });
+    let cell_a = world
+        .spawn((
+            Cell,
+            Position { x: 50., y: 50. },
+            Velocity { x: 1., y: 0. },
+            Genome::default(),
+        ))
+        .id();
+    let cell_b = world
+        .spawn((
+            Cell,
+            Position { x: 58., y: 50. },
+            Velocity { x: -2., y: 0. },
+            Genome::default(),
+        ))
+        .id();
+
+    let mut stage = SystemStage::single(collide_cells);
+    stage.run(&mut world);
+
+    // Garantieren, dass die Überlappung aufgelöst wurde
+    let position_a = *world.get::<Position>(cell_a).unwrap();
+    let position_b = *world.get::<Position>(cell_b).unwrap();
+    assert!((position_b.x - position_a.x - 10.).abs() < 1e-4);
+
+    // Garantieren, dass bei einem elastischen Stoß die Geschwindigkeiten ausgetauscht werden
+    let velocity_a = *world.get::<Velocity>(cell_a).unwrap();
+    let velocity_b = *world.get::<Velocity>(cell_b).unwrap();
+    assert!((velocity_a.x + 2.).abs() < 1e-4);
+    assert!((velocity_b.x - 1.).abs() < 1e-4);
+}
+
+#[test]
+fn genome_mutate_test() {
+    use crate::brain::MutationSettings;
+    use crate::genome::Genome;
+
+    // Genome sehr oft mit großen Änderungen mutieren lassen
+    let mutation_settings = MutationSettings {
+        genome_magnitude: 1.,
+        ..Default::default()
+    };
+    let mut genome = Genome::default();
+    let mut rng = rand::thread_rng();
+    for _ in 0..10000 {
+        genome.mutate(&mutation_settings, &mut rng);
+
+        // Garantieren, dass die Körpereigenschaften immer positiv bleiben
+        assert!(genome.size > 0.);
+        assert!(genome.speed > 0.);
+        assert!(genome.metabolism > 0.);
+    }
+
+    // Garantieren, dass ohne Mutationsstärke nichts verändert wird
+    let mutation_settings = MutationSettings {
+        genome_magnitude: 0.,
+        ..Default::default()
+    };
+    let old_genome = genome.clone();
+    genome.mutate(&mutation_settings, &mut rng);
+    assert_eq!(genome.size, old_genome.size);
+    assert_eq!(genome.speed, old_genome.speed);
+    assert_eq!(genome.metabolism, old_genome.metabolism);
+}
+
+#[test]
+fn genome_energy_drain_test() {
+    use crate::genome::{Genome, TRAIT_MIN};
+    use crate::sim::SimulationSettings;
+
+    // Garantieren, dass ein Standard-Genome genau den alten Grundverbrauch hat
+    let simulation_settings = SimulationSettings::default();
+    let genome = Genome::default();
+    assert_eq!(
+        genome.energy_drain(&simulation_settings),
+        simulation_settings.base_energy_drain
+    );
+
+    // Garantieren, dass schnellere Zellen mehr und langsamere weniger Energie verbrauchen
+    let fast_genome = Genome {
+        speed: 2.,
+        ..genome.clone()
+    };
+    let slow_genome = Genome {
+        speed: 0.5,
+        ..genome.clone()
+    };
+    assert!(
+        (fast_genome.energy_drain(&simulation_settings)
+            - (simulation_settings.base_energy_drain
+                + 3. * simulation_settings.speed_energy_drain))
+            .abs()
+            < 1e-5
+    );
+    assert!(slow_genome.energy_drain(&simulation_settings) < simulation_settings.base_energy_drain);
+
+    // Garantieren, dass auch die kleinste, langsamste Zelle keine Energie aus dem Nichts gewinnt
+    let minimal_genome = Genome {
+        size: TRAIT_MIN,
+        speed: TRAIT_MIN,
+        metabolism: TRAIT_MIN,
+        ..genome
+    };
+    assert!(minimal_genome.energy_drain(&simulation_settings) >= 0.);
+}
+
+#[test]
+fn genome_mutation_rate_test() {
+    use crate::brain::MutationSettings;
+    use crate::genome::Genome;
+
+    let mut rng = rand::thread_rng();
+
+    // Garantieren, dass sich die Mutationsrate ohne `mutation_rate_adaptation` nicht verändert
+    let mutation_settings = MutationSettings {
+        mutation_rate_adaptation: 0.,
+        ..Default::default()
+    };
+    let mut genome = Genome::default();
+    for _ in 0..100 {
+        genome.mutate(&mutation_settings, &mut rng);
+    }
+    assert_eq!(genome.mutation_rate, 1.);
+
+    // Garantieren, dass die Mutationsrate mit `mutation_rate_adaptation` mutiert und positiv bleibt
+    let mutation_settings = MutationSettings {
+        mutation_rate_adaptation: 1.,
+        ..Default::default()
+    };
+    for _ in 0..10000 {
+        genome.mutate(&mutation_settings, &mut rng);
+        assert!(genome.mutation_rate > 0.);
+    }
+    assert_ne!(genome.mutation_rate, 1.);
+
+    // Garantieren, dass `scaled` Wahrscheinlichkeiten und Stärken skaliert, aber nicht die Verteilungen
+    let mutation_settings = MutationSettings::default();
+    let scaled_mutation_settings = mutation_settings.scaled(2.);
+    assert_eq!(
+        scaled_mutation_settings.neuron_add_chance,
+        mutation_settings.neuron_add_chance * 2.
+    );
+    assert_eq!(
+        scaled_mutation_settings.weight_magnitude,
+        mutation_settings.weight_magnitude * 2.
+    );
+    assert_eq!(
+        scaled_mutation_settings.weight_distribution,
+        mutation_settings.weight_distribution
+    );
+}
+
+#[test]
+fn update_pheromones_test() {
+    use crate::sim::{update_pheromones, Chunk, ChunkRegistry, Pheromone, SimulationSettings};
+    use bevy::prelude::*;
+
+    // Welt mit 3x3 Chunks erstellen, in der nur der mittlere Chunk Pheromon enthält
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings {
+        pheromone_diffusion_rate: 0.4,
+        pheromone_decay_rate: 0.,
+        ..Default::default()
+    });
+    let mut entries = Vec::new();
+    for index in 0..3 {
+        entries.push(Vec::new());
+        for idy in 0..3 {
+            let pheromone = if (index, idy) == (1, 1) { 10. } else { 0. };
+            entries[index].push(world.spawn((Chunk, Pheromone(pheromone))).id());
+        }
+    }
+    world.spawn(ChunkRegistry {
+        chunk_size: 10.,
+        map_size: 3,
+        map_height: 3,
+        entries: entries.clone(),
+    });
+
+    let mut stage = SystemStage::single(update_pheromones);
+    stage.run(&mut world);
+
+    // Garantieren, dass sich das Pheromon gleichmäßig auf die direkten Nachbarn verteilt
+    let pheromone_at = |world: &World, index: usize, idy: usize| {
+        **world.get::<Pheromone>(entries[index][idy]).unwrap()
+    };
+    assert!((pheromone_at(&world, 1, 1) - 6.).abs() < 1e-4);
+    for (index, idy) in [(0, 1), (2, 1), (1, 0), (1, 2)] {
+        assert!((pheromone_at(&world, index, idy) - 1.).abs() < 1e-4);
+    }
+    assert_eq!(pheromone_at(&world, 0, 0), 0.);
+
+    // Garantieren, dass die Gesamtmenge ohne Zerfall erhalten bleibt und mit Zerfall abnimmt
+    let pheromone_sum = |world: &mut World| {
+        world
+            .query::<&Pheromone>()
+            .iter(world)
+            .map(|pheromone| **pheromone)
+            .sum::<f32>()
+    };
+    stage.run(&mut world);
+    assert!((pheromone_sum(&mut world) - 10.).abs() < 1e-4);
+    world
+        .resource_mut::<SimulationSettings>()
+        .pheromone_decay_rate = 0.5;
+    stage.run(&mut world);
+    assert!((pheromone_sum(&mut world) - 5.).abs() < 1e-4);
+}
+
+#[test]
+fn obstacle_test() {
+    use crate::sim::{ChunkRegistry, Obstacle, Position, WorldTopology};
+
+    let chunk_registry = ChunkRegistry {
+        chunk_size: 50.,
+        map_size: 4,
+        map_height: 4,
+        entries: Vec::new(),
+    };
+    let bounded = WorldTopology::BoundedReflective;
+    let wall = Obstacle::Segment {
+        start: Position { x: 10., y: -5. },
+        end: Position { x: 10., y: 5. },
+    };
+    let origin = Position { x: 0., y: 0. };
+
+    // Garantieren, dass eine Wand vor dem Strahl getroffen wird und neben oder hinter dem Strahl nicht
+    let ray_distance = |obstacle: &Obstacle, origin: &Position, direction: (f32, f32)| {
+        obstacle.ray_distance(origin, direction, &chunk_registry, bounded)
+    };
+    assert!((ray_distance(&wall, &origin, (1., 0.)).unwrap() - 10.).abs() < 1e-5);
+    assert!(ray_distance(&wall, &origin, (0., 1.)).is_none());
+    assert!(ray_distance(&wall, &origin, (-1., 0.)).is_none());
+    assert!(ray_distance(&wall, &Position { x: 0., y: 20. }, (1., 0.)).is_none());
+
+    // Garantieren, dass eine Zelle, die die Wand berührt, von ihr weg geschoben wird
+    let contact = |obstacle: &Obstacle, position: &Position, radius: f32| {
+        obstacle.contact(position, radius, &chunk_registry, bounded)
+    };
+    let ((normal_x, normal_y), depth) = contact(&wall, &Position { x: 8., y: 0. }, 5.).unwrap();
+    assert!((normal_x + 1.).abs() < 1e-5 && normal_y.abs() < 1e-5);
+    assert!((depth - 3.).abs() < 1e-5);
+    assert!(contact(&wall, &Position { x: 4., y: 0. }, 5.).is_none());
+
+    // Garantieren, dass Kreise ab ihrem Rand berührt werden
+    let circle = Obstacle::Circle {
+        center: Position { x: 0., y: 0. },
+        radius: 10.,
+    };
+    let ((_, normal_y), depth) = contact(&circle, &Position { x: 0., y: 12. }, 5.).unwrap();
+    assert!((normal_y - 1.).abs() < 1e-5);
+    assert!((depth - 3.).abs() < 1e-5);
+    assert!(contact(&circle, &Position { x: 0., y: 16. }, 5.).is_none());
+
+    // Garantieren, dass eine schnelle Zelle vor der Wand anhält statt hindurch zu springen
+    let sweep_distance = |obstacle: &Obstacle, origin: &Position, direction: (f32, f32)| {
+        obstacle.sweep_distance(origin, direction, 1., &chunk_registry, bounded)
+    };
+    let distance = sweep_distance(&wall, &origin, (1., 0.)).unwrap();
+    assert!(distance < 10. && distance > 9.9);
+    assert!(sweep_distance(&wall, &origin, (-1., 0.)).is_none());
+    let distance = sweep_distance(&circle, &Position { x: 0., y: 30. }, (0., -1.)).unwrap();
+    assert!(distance > 19. && distance < 19.1);
+    assert!(sweep_distance(&circle, &Position { x: 0., y: 5. }, (0., -1.)).is_none());
+
+    // Garantieren, dass Hindernisse bei einem Torus auch über den Rand der Map hinweg wirken
+    let edge_circle = Obstacle::Circle {
+        center: Position { x: 2., y: 100. },
+        radius: 5.,
+    };
+    let edge_position = Position { x: 198., y: 100. };
+    let ((normal_x, _), depth) = edge_circle
+        .contact(&edge_position, 2., &chunk_registry, WorldTopology::Torus)
+        .unwrap();
+    assert!((normal_x + 1.).abs() < 1e-5);
+    assert!((depth - 3.).abs() < 1e-5);
+    assert!(contact(&edge_circle, &edge_position, 2.).is_none());
+    assert!(
+        (edge_circle
+            .ray_distance(
+                &Position { x: 190., y: 100. },
+                (1., 0.),
+                &chunk_registry,
+                WorldTopology::Torus
+            )
+            .unwrap()
+            - 7.)
+            .abs()
+            < 1e-5
+    );
+}
+
+#[test]
+fn obstacle_tunneling_test() {
+    use crate::brain::Brain;
+    use crate::genome::Genome;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{
+        collide_obstacles, tick_cells, CellBundle, Obstacle, Position, SimulationSettings, Velocity,
+    };
+    use bevy::prelude::*;
+
+    // Welt mit einer Wand erstellen
+    let mut world = tick_world(SimulationSettings {
+        energy_required_for_split: f32::MAX,
+        ..default()
+    });
+    world.spawn(Obstacle::Segment {
+        start: Position { x: 105., y: 50. },
+        end: Position { x: 105., y: 150. },
+    });
+
+    // Kleine Zelle, die sich pro Tick viel weiter als ihren Radius bewegt
+    let cell_entity = world
+        .spawn(CellBundle {
+            position: Position { x: 100., y: 100. },
+            velocity: Velocity { x: 20., y: 0. },
+            genome: Genome {
+                size: 0.2,
+                ..default()
+            },
+            brain: Brain::new(&SenseRegistry::default()),
+            ..default()
+        })
+        .id();
+    let mut stage = SystemStage::single_threaded()
+        .with_system(tick_cells)
+        .with_system(collide_obstacles.after(tick_cells));
+
+    // Garantieren, dass die Zelle vor der Wand bleibt und nicht weiter in die Wand hinein fliegt
+    for _ in 0..5 {
+        stage.run(&mut world);
+        let position = world.get::<Position>(cell_entity).unwrap();
+        assert!(
+            position.x < 105.,
+            "Zelle ist durch die Wand gesprungen: {position:?}"
+        );
+        assert!(world.get::<Velocity>(cell_entity).unwrap().x <= 0.);
+    }
+}
+
+#[test]
+fn create_world_test() {
+    use crate::sim::{
+        create_world, Cell, Chunk, ChunkRegistry, ChunkSettings, CreateWorld, Food, Foodlist,
+        Pheromone, Position, SimulationSettings,
+    };
+    use bevy::prelude::*;
+
+    // Welt mit 2 x 2 Chunks erstellen, der Chunk oben links hat eigene Settings und Pheromon
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings::default());
+    world.init_resource::<Events<CreateWorld>>();
+    let mut commands_queue = bevy::ecs::system::CommandQueue::default();
+    let mut commands = Commands::new(&mut commands_queue, &world);
+    let chunk_registry = ChunkRegistry::spawn(&mut commands, 50., 2, 2, |index, idy| {
+        if (index, idy) == (0, 1) {
+            crate::sim::ChunkBundle {
+                chunk_settings: ChunkSettings {
+                    spawn_chance: 0.5,
+                    ..Default::default()
+                },
+                pheromone: Pheromone(3.),
+                ..Default::default()
+            }
+        } else {
+            Default::default()
+        }
+    });
+    commands.spawn(chunk_registry);
+    commands_queue.apply(&mut world);
+    let cell = world.spawn((Cell, Position { x: 25., y: 75. })).id();
+    let food = world.spawn((Food, Position { x: 90., y: 10. })).id();
+
+    // Auf eine nicht quadratische Map mit 4 x 1 Chunks der Größe 100 wechseln
+    world
+        .resource_mut::<Events<CreateWorld>>()
+        .send(CreateWorld {
+            map_width: 4,
+            map_height: 1,
+            chunk_size: 100.,
+        });
+    let mut stage = SystemStage::single(create_world);
+    stage.run(&mut world);
+
+    // Garantieren, dass das Chunk-Registry die neue Map beschreibt und nur noch die neuen Chunks existieren
+    let chunk_registry = world.query::<&ChunkRegistry>().single(&world);
+    assert_eq!((chunk_registry.map_size, chunk_registry.map_height), (4, 1));
+    assert_eq!(chunk_registry.map_length(), (400., 100.));
+    let entries = chunk_registry.entries.clone();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<Chunk>>()
+            .iter(&world)
+            .count(),
+        4
+    );
+
+    // Garantieren, dass Zellen und Nahrung im Verhältnis verschoben werden
+    let cell_position = *world.get::<Position>(cell).unwrap();
+    assert!((cell_position.x - 100.).abs() < 1e-4 && (cell_position.y - 75.).abs() < 1e-4);
+    let food_position = *world.get::<Position>(food).unwrap();
+    assert!((food_position.x - 360.).abs() < 1e-4 && (food_position.y - 10.).abs() < 1e-4);
+
+    // Garantieren, dass die Nahrung in der Foodlist ihres neuen Chunks steht
+    assert_eq!(**world.get::<Foodlist>(entries[3][0]).unwrap(), vec![food]);
+    assert!(world.get::<Foodlist>(entries[0][0]).unwrap().is_empty());
+
+    // Garantieren, dass die Chunks die Werte des alten Chunks an der gleichen relativen Stelle übernehmen
+    // (die Mitte der neuen Chunks liegt auf halber Höhe, also in den oberen alten Chunks)
+    for (index, pheromone, spawn_chance) in [
+        (0, 3., 0.5),
+        (1, 3., 0.5),
+        (2, 0., ChunkSettings::default().spawn_chance),
+    ] {
+        assert_eq!(
+            **world.get::<Pheromone>(entries[index][0]).unwrap(),
+            pheromone
+        );
+        assert_eq!(
+            world
+                .get::<ChunkSettings>(entries[index][0])
+                .unwrap()
+                .spawn_chance,
+            spawn_chance
+        );
+    }
+
+    // Garantieren, dass die Simulation-Settings die neue Map übernehmen
+    let simulation_settings = world.resource::<SimulationSettings>();
+    assert_eq!(
+        (
+            simulation_settings.map_width,
+            simulation_settings.map_height
+        ),
+        (4, 1)
+    );
+}
+
+#[test]
+fn create_world_while_running_test() {
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{
+        create_world, spawn_food, tick_cells, CellBundle, Chunk, ChunkRegistry, ChunkSettings,
+        CreateWorld, Food, Foodlist, Position, SimulationSettings,
+    };
+    use bevy::prelude::*;
+
+    // Laufende Welt mit einer Zelle erstellen, in der jeder Chunk Nahrung spawnt
+    let mut world = tick_world(SimulationSettings {
+        energy_required_for_split: f32::MAX,
+        ..default()
+    });
+    world.init_resource::<Events<CreateWorld>>();
+    for mut chunk_settings in world
+        .query_filtered::<&mut ChunkSettings, With<Chunk>>()
+        .iter_mut(&mut world)
+    {
+        chunk_settings.spawn_chance = 1.;
+    }
+    let cell_entity = world
+        .spawn(CellBundle {
+            position: Position { x: 100., y: 100. },
+            brain: Brain::new(&SenseRegistry::default()),
+            ..default()
+        })
+        .id();
+
+    // Neue Welt im gleichen Frame wie ein Tick erstellen (wie in `SimulationPlugin` registriert)
+    world
+        .resource_mut::<Events<CreateWorld>>()
+        .send(CreateWorld {
+            map_width: 3,
+            map_height: 2,
+            chunk_size: 40.,
+        });
+    let mut stage = SystemStage::parallel()
+        .with_system(create_world.at_start())
+        .with_system(spawn_food)
+        .with_system(tick_cells.after(spawn_food));
+    stage.run(&mut world);
+
+    // Garantieren, dass der Tick auf den neuen Chunks gelaufen ist und die Nahrung in ihnen liegt
+    let chunk_registry = world.query::<&ChunkRegistry>().single(&world);
+    assert_eq!((chunk_registry.map_size, chunk_registry.map_height), (3, 2));
+    let entries = chunk_registry.entries.clone();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<Chunk>>()
+            .iter(&world)
+            .count(),
+        6
+    );
+    let foodlist_total: usize = entries
+        .iter()
+        .flatten()
+        .map(|chunk_entity| world.get::<Foodlist>(*chunk_entity).unwrap().len())
+        .sum();
+    assert!(foodlist_total > 0);
+    assert_eq!(
+        foodlist_total,
+        world
+            .query_filtered::<(), With<Food>>()
+            .iter(&world)
+            .count()
+    );
+    assert!(world.get::<Position>(cell_entity).is_some());
+}
+
+#[test]
+fn paint_chunk_settings_test() {
+    use crate::sim::{
+        paint_chunk_settings, ChunkRegistry, ChunkSettings, PaintChunkSettings, Position,
+        SimulationSettings,
+    };
+    use bevy::prelude::*;
+
+    // Welt mit einer Reihe aus 4 Chunks der Größe 10 erstellen
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings::default());
+    world.init_resource::<Events<PaintChunkSettings>>();
+    let mut commands_queue = bevy::ecs::system::CommandQueue::default();
+    let mut commands = Commands::new(&mut commands_queue, &world);
+    let chunk_registry = ChunkRegistry::spawn(&mut commands, 10., 4, 1, |_, _| Default::default());
+    let entries = chunk_registry.entries.clone();
+    commands.spawn(chunk_registry);
+    commands_queue.apply(&mut world);
+
+    // Am linken Rand des ersten Chunks nur die Spawn chance malen (die Mitte des zweiten Chunks liegt im Abfall)
+    let paint_chunk_settings_event = |duration: f32| PaintChunkSettings {
+        center: Position { x: 1., y: 5. },
+        radius: 20.,
+        falloff: 0.5,
+        duration,
+        spawn_chance: Some(1.),
+        spawned_food_energy: None,
+        velocity_damping: None,
+    };
+    assert_eq!(paint_chunk_settings_event(1.).strength(5.), 1.);
+    assert!((paint_chunk_settings_event(1.).strength(15.) - 0.5).abs() < 1e-6);
+    assert_eq!(paint_chunk_settings_event(1.).strength(25.), 0.);
+    world
+        .resource_mut::<Events<PaintChunkSettings>>()
+        .send(paint_chunk_settings_event(1.));
+    let mut stage = SystemStage::single(paint_chunk_settings);
+    stage.run(&mut world);
+
+    // Garantieren, dass die Chunks abhängig von der Entfernung ihrer Mitte gesetzt werden
+    let default_spawn_chance = ChunkSettings::default().spawn_chance;
+    let spawn_chances: Vec<f32> = entries
+        .iter()
+        .map(|column| world.get::<ChunkSettings>(column[0]).unwrap().spawn_chance)
+        .collect();
+    assert_eq!(spawn_chances[0], 1.);
+    assert!(
+        (spawn_chances[1] - (default_spawn_chance + (1. - default_spawn_chance) * 0.6)).abs()
+            < 1e-6
+    );
+    assert_eq!(spawn_chances[2], default_spawn_chance);
+    assert_eq!(spawn_chances[3], default_spawn_chance);
+
+    // Garantieren, dass zwei halb so lange Pinselstriche (z.B. bei doppelter Framerate) gleich wirken wie einer
+    world
+        .get_mut::<ChunkSettings>(entries[1][0])
+        .unwrap()
+        .spawn_chance = default_spawn_chance;
+    for _ in 0..2 {
+        world
+            .resource_mut::<Events<PaintChunkSettings>>()
+            .send(paint_chunk_settings_event(0.5));
+    }
+    stage.run(&mut world);
+    assert!(
+        (world
+            .get::<ChunkSettings>(entries[1][0])
+            .unwrap()
+            .spawn_chance
+            - spawn_chances[1])
+            .abs()
+            < 1e-6
+    );
+
+    // Garantieren, dass nicht gemalte Werte unverändert bleiben
+    for column in &entries {
+        let chunk_settings = world.get::<ChunkSettings>(column[0]).unwrap();
+        assert_eq!(
+            chunk_settings.velocity_damping,
+            ChunkSettings::default().velocity_damping
+        );
+    }
+}
+
+#[test]
+fn chunk_settings_image_test() {
+    use crate::sim::{
+        export_chunk_settings, import_chunk_settings, ChunkRegistry, ChunkSettings,
+        ExportChunkSettings, ImportChunkSettings,
+    };
+    use bevy::prelude::*;
+
+    // Welt mit 3 x 2 Chunks erstellen, jeder Chunk hat andere Settings
+    let mut world = World::new();
+    world.init_resource::<Events<ImportChunkSettings>>();
+    world.init_resource::<Events<ExportChunkSettings>>();
+    let mut commands_queue = bevy::ecs::system::CommandQueue::default();
+    let mut commands = Commands::new(&mut commands_queue, &world);
+    let chunk_settings_at = |index: u32, idy: u32| ChunkSettings {
+        spawn_chance: 0.02 * index as f32,
+        spawned_food_energy: 100. * idy as f32,
+        velocity_damping: 0.2 * (index + idy) as f32,
+    };
+    let chunk_registry = ChunkRegistry::spawn(&mut commands, 10., 3, 2, |index, idy| {
+        crate::sim::ChunkBundle {
+            chunk_settings: chunk_settings_at(index, idy),
+            ..Default::default()
+        }
+    });
+    let entries = chunk_registry.entries.clone();
+    commands.spawn(chunk_registry);
+    commands_queue.apply(&mut world);
+    let mut stage = SystemStage::parallel()
+        .with_system(export_chunk_settings)
+        .with_system(import_chunk_settings.after(export_chunk_settings));
+
+    for extension in ["png", "pgm"] {
+        let path = std::env::temp_dir()
+            .join(format!("die_ursuppe_chunk_settings_test.{}", extension))
+            .to_string_lossy()
+            .to_string();
+
+        // Exportieren, alle Chunks zurücksetzen und wieder importieren
+        world
+            .resource_mut::<Events<ExportChunkSettings>>()
+            .send(ExportChunkSettings {
+                path: path.clone(),
+                scale: default(),
+            });
+        stage.run(&mut world);
+        for mut chunk_settings in world.query::<&mut ChunkSettings>().iter_mut(&mut world) {
+            *chunk_settings = ChunkSettings::default();
+        }
+        world
+            .resource_mut::<Events<ImportChunkSettings>>()
+            .send(ImportChunkSettings {
+                path: path.clone(),
+                scale: default(),
+            });
+        stage.run(&mut world);
+        std::fs::remove_file(&path).unwrap();
+
+        // Garantieren, dass die Werte bis auf die Rundung auf 256 Stufen erhalten bleiben
+        // (Graustufen-Bilder enthalten nur die Spawn chance)
+        for (index, column) in entries.iter().enumerate() {
+            for (idy, chunk_entity) in column.iter().enumerate() {
+                let expected = chunk_settings_at(index as u32, idy as u32);
+                let chunk_settings = world.get::<ChunkSettings>(*chunk_entity).unwrap();
+                assert!((chunk_settings.spawn_chance - expected.spawn_chance).abs() < 0.001);
+                if extension == "pgm" {
+                    assert_eq!(
+                        chunk_settings.spawned_food_energy,
+                        ChunkSettings::default().spawned_food_energy
+                    );
+                } else {
+                    assert!(
+                        (chunk_settings.spawned_food_energy - expected.spawned_food_energy).abs()
+                            < 1.
+                    );
+                    assert!(
+                        (chunk_settings.velocity_damping - expected.velocity_damping).abs() < 0.01
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn environment_schedule_test() {
+    use crate::sim::{ChunkRegistry, EnvironmentEffect, EnvironmentSchedule, Position};
+    use bevy::prelude::*;
+
+    // Map mit 10 x 10 Chunks der Größe 10
+    let chunk_registry = ChunkRegistry {
+        chunk_size: 10.,
+        map_size: 10,
+        map_height: 10,
+        entries: vec![vec![Entity::from_raw(0); 10]; 10],
+    };
+    let position = Position { x: 5., y: 5. };
+    let food_spawn_factor = |effect: EnvironmentEffect, position: &Position, tick: u64| {
+        EnvironmentSchedule {
+            effects: vec![effect],
+        }
+        .food_spawn_factor(position, tick, &chunk_registry)
+    };
+
+    // Garantieren, dass ein leerer Schedule nichts verändert
+    assert_eq!(
+        EnvironmentSchedule::default().food_spawn_factor(&position, 123, &chunk_registry),
+        1.
+    );
+
+    // Garantieren, dass die Jahreszeiten sinusförmig schwanken
+    let season = || EnvironmentEffect::Season {
+        period: 100,
+        amplitude: 0.5,
+        phase: 0.,
+    };
+    assert!((food_spawn_factor(season(), &position, 0) - 1.).abs() < 1e-5);
+    assert!((food_spawn_factor(season(), &position, 25) - 1.5).abs() < 1e-5);
+    assert!((food_spawn_factor(season(), &position, 175) - 0.5).abs() < 1e-5);
+
+    // Garantieren, dass der Hotspot wandert und am Rand auf die gegenüberliegende Seite wechselt
+    let hotspot = || EnvironmentEffect::Hotspot {
+        start: Position { x: 5., y: 5. },
+        velocity: Position { x: 1., y: 0. },
+        radius: 20.,
+        strength: 2.,
+    };
+    assert!((food_spawn_factor(hotspot(), &position, 0) - 3.).abs() < 1e-5);
+    assert!((food_spawn_factor(hotspot(), &position, 10) - 2.).abs() < 1e-5);
+    assert!((food_spawn_factor(hotspot(), &position, 50) - 1.).abs() < 1e-5);
+    assert!((food_spawn_factor(hotspot(), &position, 90) - 2.).abs() < 1e-5);
+
+    // Garantieren, dass Dürren nur in ihren Zeiträumen wirken und sich wiederholen
+    let drought = |interval: u64| EnvironmentEffect::Drought {
+        start_tick: 100,
+        duration: 10,
+        interval,
+        factor: 0.1,
+    };
+    for (tick, interval, expected) in [
+        (99, 0, 1.),
+        (100, 0, 0.1),
+        (109, 0, 0.1),
+        (110, 0, 1.),
+        (1005, 0, 1.),
+        (1005, 100, 0.1),
+        (1015, 100, 1.),
+    ] {
+        assert_eq!(
+            food_spawn_factor(drought(interval), &position, tick),
+            expected
+        );
+    }
+}
+
+#[test]
+fn light_level_test() {
+    use crate::sim::SimulationSettings;
+
+    // Garantieren, dass es ohne Tag-Nacht-Zyklus immer hell ist
+    let mut simulation_settings = SimulationSettings::default();
+    assert_eq!(simulation_settings.light_level(0), 1.);
+    assert_eq!(simulation_settings.light_level(1234), 1.);
+
+    // Garantieren, dass die Helligkeit mit `day_length` periodisch zwischen 0 und 1 schwankt
+    simulation_settings.day_length = 100;
+    assert!((simulation_settings.light_level(0) - 0.5).abs() < 1e-5);
+    assert!((simulation_settings.light_level(25) - 1.).abs() < 1e-5);
+    assert!((simulation_settings.light_level(75)).abs() < 1e-5);
+    assert!((simulation_settings.light_level(125) - 1.).abs() < 1e-5);
+
+    // Garantieren, dass der Faktor zwischen Nacht und Mittag interpoliert wird
+    assert_eq!(SimulationSettings::daylight_factor(0., 0.5), 0.5);
+    assert_eq!(SimulationSettings::daylight_factor(1., 0.5), 1.);
+    assert_eq!(SimulationSettings::daylight_factor(0.5, 0.2), 0.6);
+}
+
+#[test]
+fn night_perception_test() {
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{
+        tick_cells, CellBundle, ChunkRegistry, Energy, FoodBundle, Foodlist, Position,
+        SimulationSettings, TickCount,
+    };
+    use bevy::prelude::*;
+
+    // Welt mit Tag-Nacht-Zyklus und einem Sehstrahl erstellen (Tick 25 ist Mittag, Tick 75 Mitternacht)
+    let simulation_settings = SimulationSettings {
+        day_length: 100,
+        night_sensing_factor: 0.5,
+        sensing_radius: 50.,
+        vision_range: 100.,
+        vision_ray_count: 1,
+        energy_required_for_split: f32::MAX,
+        ..default()
+    };
+    let food_radius = simulation_settings.food_radius;
+    let sense_registry = SenseRegistry::default();
+
+    // Der Brain schreibt die Sensoren mit kleinem Gewicht in Output-Neuronen ohne Wirkung
+    // (die Input-Neuronen selbst werden von `Brain::tick` überschrieben)
+    let mut brain = Brain::new(&sense_registry);
+    connect(
+        &mut brain,
+        &sense_registry,
+        "Nearest food distance",
+        "Child wish",
+        1e-4,
+    );
+    connect(
+        &mut brain,
+        &sense_registry,
+        "Ray 0 distance",
+        "Signal 0",
+        0.5,
+    );
+    let food_distance_neuron_index = actuator_neuron_index(&sense_registry, "Child wish");
+    let ray_distance_neuron_index = actuator_neuron_index(&sense_registry, "Signal 0");
+    let mut world = tick_world(simulation_settings);
+
+    // Nahrung 30 vor der Zelle (bei Nacht außerhalb des `sensing_radius`, aber innerhalb der `vision_range`)
+    let food_position = Position { x: 130., y: 100. };
+    let food_entity = world
+        .spawn(FoodBundle {
+            position: food_position,
+            energy: Energy(1.),
+            ..default()
+        })
+        .id();
+    let chunk_entity = world
+        .query::<&ChunkRegistry>()
+        .single(&world)
+        .chunk_at(&food_position)
+        .unwrap();
+    world
+        .get_mut::<Foodlist>(chunk_entity)
+        .unwrap()
+        .push(food_entity);
+    let cell_entity = world.spawn(CellBundle { brain, ..default() }).id();
+    let mut stage = SystemStage::single(tick_cells);
+    let mut perceive = |world: &mut World, tick_count: u64| {
+        **world.resource_mut::<TickCount>() = tick_count;
+        *world.get_mut::<Position>(cell_entity).unwrap() = Position { x: 100., y: 100. };
+        stage.run(world);
+        let brain = world.get::<Brain>(cell_entity).unwrap();
+        (
+            brain
+                .read_neuron(food_distance_neuron_index)
+                .unwrap()
+                .atanh()
+                / 1e-4,
+            brain
+                .read_neuron(ray_distance_neuron_index)
+                .unwrap()
+                .atanh()
+                / 0.5,
+        )
+    };
+
+    // Garantieren, dass Distanzen am Tag relativ zu den vollen Reichweiten sind
+    let (food_distance, ray_distance) = perceive(&mut world, 25);
+    assert!((food_distance - 900.).abs() < 0.1);
+    assert!((ray_distance - (30. - food_radius) / 100.).abs() < 1e-4);
+
+    // Garantieren, dass in der Nacht nur weiter entfernte Objekte abgeschnitten werden
+    // und wahrgenommene Distanzen gleich bleiben
+    let (food_distance, ray_distance) = perceive(&mut world, 75);
+    assert!((food_distance - 2500.).abs() < 0.1);
+    assert!((ray_distance - (30. - food_radius) / 100.).abs() < 1e-4);
+
+    // Garantieren, dass ohne Sicht in der Nacht nichts als berührend wahrgenommen wird
+    world
+        .resource_mut::<SimulationSettings>()
+        .night_sensing_factor = 0.;
+    let (food_distance, ray_distance) = perceive(&mut world, 75);
+    assert!((food_distance - 2500.).abs() < 0.1);
+    assert!((ray_distance - 1.).abs() < 1e-4);
+}
+
+#[test]
+fn update_species_statistic_test() {
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{
+        update_species, Cell, SimulationSettings, Species, SpeciesRegistry, TickCount,
+        EXTINCT_SPECIES_GRACE_PERIOD,
+    };
+    use crate::ui::{SpeciesStatistic, StatisticData};
+    use bevy::prelude::*;
+
+    // Welt mit einer Zelle ohne Species erstellen
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings::default());
+    world.insert_resource(SpeciesRegistry::default());
+    world.insert_resource(TickCount(1));
+    world.spawn((StatisticData::default(), SpeciesStatistic::default()));
+    let cell_entity = world
+        .spawn((
+            Cell,
+            Brain::new(&SenseRegistry::default()),
+            Species::default(),
+        ))
+        .id();
+    let mut stage = SystemStage::single(update_species);
+
+    // Garantieren, dass die Zelle sofort eingeteilt wird und eine Linie in der Statistik bekommt
+    stage.run(&mut world);
+    assert_eq!(world.get::<Species>(cell_entity).unwrap().id, 1);
+    let line_count = |world: &mut World| {
+        world
+            .query::<(&StatisticData, &SpeciesStatistic)>()
+            .single(world)
+            .1
+            .species_ids
+            .len()
+    };
+    assert_eq!(line_count(&mut world), 1);
+
+    // Garantieren, dass die Linie der ausgestorbenen Species erst nach der Schonfrist entfernt wird
+    world.despawn(cell_entity);
+    for _ in 0..=EXTINCT_SPECIES_GRACE_PERIOD {
+        **world.resource_mut::<TickCount>() += 1;
+        stage.run(&mut world);
+        assert_eq!(line_count(&mut world), 1);
+    }
+    **world.resource_mut::<TickCount>() += 1;
+    stage.run(&mut world);
+    assert_eq!(line_count(&mut world), 0);
+    let statistic_data = world.query::<&StatisticData>().single(&world);
+    assert!(statistic_data.lines.is_empty());
+}
+
+#[test]
+fn attack_and_corpse_test() {
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{
+        despawn_cells, tick_cells, CellBundle, ChunkRegistry, Energy, Food, Foodlist, Position,
+        SimulationSettings, Velocity, WorldTopology,
+    };
+    use bevy::{prelude::*, reflect::GetPath};
+
+    // Welt ohne Energieverbrauch (außer durch Angriffe) und ohne Fortpflanzung erstellen
+    let simulation_settings = SimulationSettings {
+        base_energy_drain: 0.,
+        speed_energy_drain: 0.,
+        age_energy_drain: 0.,
+        energy_required_for_split: f32::MAX,
+        cell_collisions: false,
+        leave_corpses: true,
+        corpse_body_energy: 0.1,
+        ..default()
+    };
+    let (attack_drain, attack_efficiency, attack_cost) = (
+        simulation_settings.attack_drain,
+        simulation_settings.attack_efficiency,
+        simulation_settings.attack_cost,
+    );
+    let mut world = tick_world(simulation_settings);
+    let sense_registry = SenseRegistry::default();
+
+    // Angreifer (Attack-Output immer fast 1) und Ziel berühren sich
+    let mut attacker_brain = Brain::new(&sense_registry);
+    *attacker_brain
+        .get_path_mut::<f32>(&format!(
+            "neurons[{}].bias",
+            actuator_neuron_index(&sense_registry, "Attack")
+        ))
+        .unwrap() = 10.;
+    let attacker_entity = world
+        .spawn(CellBundle {
+            position: Position { x: 100., y: 100. },
+            energy: Energy(50.),
+            brain: attacker_brain,
+            ..default()
+        })
+        .id();
+    let target_entity = world
+        .spawn(CellBundle {
+            position: Position { x: 101., y: 100. },
+            energy: Energy(50.),
+            brain: Brain::new(&sense_registry),
+            ..default()
+        })
+        .id();
+    let mut stage = SystemStage::single(tick_cells);
+    let energy = |world: &World, entity: Entity| **world.get::<Energy>(entity).unwrap();
+
+    // Garantieren, dass das Ziel `attack_drain` verliert und der Angreifer davon den Anteil `attack_efficiency` abzüglich `attack_cost` bekommt
+    stage.run(&mut world);
+    assert!((energy(&world, target_entity) - (50. - attack_drain)).abs() < 1e-4);
+    assert!(
+        (energy(&world, attacker_entity) - (50. + attack_drain * attack_efficiency - attack_cost))
+            .abs()
+            < 1e-4
+    );
+
+    // Garantieren, dass einem Ziel höchstens seine ganze Energie abgezogen wird
+    world.get_mut::<Energy>(target_entity).unwrap().0 = 0.5;
+    let attacker_energy = energy(&world, attacker_entity);
+    stage.run(&mut world);
+    assert!(energy(&world, target_entity).abs() < 1e-4);
+    assert!(
+        (energy(&world, attacker_entity)
+            - (attacker_energy + 0.5 * attack_efficiency - attack_cost))
+            .abs()
+            < 1e-4
+    );
+
+    // Garantieren, dass das tote Ziel einen Kadaver in seinem Chunk hinterlässt,
+    // der den Teil seiner Energie enthält, den der Angreifer nicht bekommen hat
+    let mut despawn_stage = SystemStage::single(despawn_cells);
+    despawn_stage.run(&mut world);
+    assert!(world.get_entity(target_entity).is_none());
+    assert!(world.get_entity(attacker_entity).is_some());
+    let take_corpses = |world: &mut World| {
+        let corpses: Vec<(Entity, Position, f32)> = world
+            .query_filtered::<(Entity, &Position, &Energy), With<Food>>()
+            .iter(world)
+            .map(|(entity, position, energy)| (entity, *position, **energy))
+            .collect();
+        for (corpse_entity, ..) in &corpses {
+            world.despawn(*corpse_entity);
+        }
+        corpses
+    };
+    let corpses = take_corpses(&mut world);
+    assert_eq!(corpses.len(), 1);
+    let (corpse_entity, corpse_position, corpse_energy) = corpses[0];
+    assert!((corpse_energy - 0.5 * (1. - attack_efficiency)).abs() < 1e-5);
+    let chunk_entity = world
+        .query::<&ChunkRegistry>()
+        .single(&world)
+        .chunk_at(&corpse_position)
+        .unwrap();
+    assert!(world
+        .get::<Foodlist>(chunk_entity)
+        .unwrap()
+        .contains(&corpse_entity));
+
+    // Garantieren, dass eine verhungerte Zelle einen Kadaver mit `corpse_body_energy` hinterlässt
+    world.get_mut::<Energy>(attacker_entity).unwrap().0 = -0.5;
+    despawn_stage.run(&mut world);
+    let corpses = take_corpses(&mut world);
+    assert_eq!(corpses.len(), 1);
+    assert_eq!(corpses[0].2, 0.1);
+
+    // Garantieren, dass eine Zelle, die bei `BoundedLethal` über den Rand fliegt,
+    // ihre ganze Energie in einem Kadaver am Rand hinterlässt
+    world.resource_mut::<SimulationSettings>().world_topology = WorldTopology::BoundedLethal;
+    let (map_width, _) = world.query::<&ChunkRegistry>().single(&world).map_length();
+    world.spawn(CellBundle {
+        position: Position {
+            x: map_width - 1.,
+            y: 100.,
+        },
+        velocity: Velocity { x: 20., y: 0. },
+        energy: Energy(20.),
+        brain: Brain::new(&sense_registry),
+        ..default()
+    });
+    stage.run(&mut world);
+    despawn_stage.run(&mut world);
+    let corpses = take_corpses(&mut world);
+    assert_eq!(corpses.len(), 1);
+    let (_, corpse_position, corpse_energy) = corpses[0];
+    assert!(corpse_position.x < map_width);
+    assert!((corpse_energy - 20.).abs() < 1e-4);
+}
+
+#[test]
+fn signal_reception_test() {
+    use crate::brain::Brain;
+    use crate::senses::SenseRegistry;
+    use crate::sim::{tick_cells, CellBundle, Position, Signals, SimulationSettings};
+    use bevy::prelude::*;
+
+    // Welt mit zwei Signal-Kanälen ohne Energieverbrauch und ohne Fortpflanzung erstellen
+    let simulation_settings = SimulationSettings {
+        signal_channel_count: 2,
+        signal_range: 50.,
+        energy_required_for_split: f32::MAX,
+        cell_collisions: false,
+        ..default()
+    };
+    let mut world = tick_world(simulation_settings);
+    let sense_registry = SenseRegistry::with_signal_channels(2);
+
+    // Der Empfänger sendet auf jedem Kanal das, was er auf diesem Kanal empfängt
+    let mut receiver_brain = Brain::new(&sense_registry);
+    for channel in 0..2 {
+        connect(
+            &mut receiver_brain,
+            &sense_registry,
+            &format!("Signal {channel} received"),
+            &format!("Signal {channel}"),
+            1.,
+        );
+    }
+    let receiver_entity = world
+        .spawn(CellBundle {
+            position: Position { x: 100., y: 100. },
+            brain: receiver_brain,
+            ..default()
+        })
+        .id();
+
+    // Sender in verschiedenen Entfernungen (der letzte ist außerhalb von `signal_range`)
+    for (position, signals) in [
+        (Position { x: 110., y: 100. }, [1., 0., 0., 0.]),
+        (Position { x: 100., y: 130. }, [0.5, 1., 0., 0.]),
+        (Position { x: 160., y: 100. }, [1., 1., 0., 0.]),
+    ] {
+        world.spawn(CellBundle {
+            position,
+            signals: Signals(signals),
+            brain: Brain::new(&sense_registry),
+            ..default()
+        });
+    }
+    SystemStage::single(tick_cells).run(&mut world);
+
+    // Garantieren, dass die Signale linear mit der Entfernung schwächer empfangen werden
+    // (Kanal 0: 1 * 0.8 + 0.5 * 0.4, Kanal 1: 1 * 0.4)
+    let signals = **world.get::<Signals>(receiver_entity).unwrap();
+    assert!((signals[0] - 1_f32.tanh()).abs() < 1e-5);
+    assert!((signals[1] - 0.4_f32.tanh()).abs() < 1e-5);
+    assert_eq!(signals[2], 0.);
+    assert_eq!(signals[3], 0.);
+}
+
+#[test]
+fn signal_channel_count_test() {
+    use crate::brain::Brain;
+    use crate::senses::{SenseRegistry, SIGNAL_CHANNEL_COUNT_MAX};
+    use crate::sim::{update_sense_registry, SimulationSettings};
+    use bevy::prelude::*;
+
+    // Garantieren, dass es nur für die verwendeten Kanäle Signal-Neuronen gibt (höchstens `SIGNAL_CHANNEL_COUNT_MAX`)
+    let base_neuron_count = SenseRegistry::with_signal_channels(0).neuron_count();
+    for signal_channel_count in 0..=SIGNAL_CHANNEL_COUNT_MAX as u32 + 2 {
+        let sense_registry = SenseRegistry::with_signal_channels(signal_channel_count);
+        let used_channel_count = signal_channel_count.min(SIGNAL_CHANNEL_COUNT_MAX as u32);
+        assert_eq!(sense_registry.signal_channel_count(), used_channel_count);
+        assert_eq!(
+            sense_registry.neuron_count(),
+            base_neuron_count + 2 * used_channel_count as usize
+        );
+        assert_eq!(
+            sense_registry
+                .sensors()
+                .iter()
+                .filter(|sensor| sensor.name.starts_with("Signal"))
+                .count(),
+            used_channel_count as usize
+        );
+    }
+
+    // Welt mit einem Brain für zwei Kanäle erstellen
+    let mut world = World::new();
+    world.insert_resource(SimulationSettings {
+        signal_channel_count: 2,
+        ..default()
+    });
+    world.insert_resource(SenseRegistry::with_signal_channels(2));
+    let brain_entity = world
+        .spawn(Brain::new(&SenseRegistry::with_signal_channels(2)))
+        .id();
+    let mut stage = SystemStage::single(update_sense_registry);
+    stage.run(&mut world);
+    assert_eq!(world.resource::<SenseRegistry>().signal_channel_count(), 2);
+
+    // Garantieren, dass die SenseRegistry bei weniger Kanälen neu gebaut und das Brain angepasst wird,
+    // ohne dass die Neuronen der abgeschalteten Kanäle verloren gehen
+    world
+        .resource_mut::<SimulationSettings>()
+        .signal_channel_count = 1;
+    stage.run(&mut world);
+    let sense_registry = world.resource::<SenseRegistry>();
+    assert_eq!(sense_registry.signal_channel_count(), 1);
+    let brain = world.get::<Brain>(brain_entity).unwrap();
+    assert!(brain
+        .neurons()
+        .iter()
+        .map(|neuron| neuron.id)
+        .take(sense_registry.neuron_count())
+        .eq(sense_registry.neuron_ids()));
+    assert_eq!(
+        brain.neurons().len(),
+        SenseRegistry::with_signal_channels(2).neuron_count()
+    );
+}
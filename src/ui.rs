@@ -1,22 +1,43 @@
-use crate::brain::{Brain, IMMUNE_NEURON_COUNT};
+use crate::brain::{Brain, MutationSettings, PerturbationDistribution};
+use crate::genome::Genome;
+use crate::senses::{SenseRegistry, SIGNAL_CHANNEL_COUNT_MAX, VISION_RAY_COUNT_MAX};
 use crate::sim::{
-    ApplyChunkSettings, ApplySimulationSettings, Cell, CellStats, Clear, Energy, Food, Load,
-    Position, Save, SimulationSettings, SpawnCell, TogglePause,
+    ApplyChunkSettings, ApplyMutationSettings, ApplySimulationSettings, Cell, CellStats, Chunk,
+    ChunkRegistry, ChunkSettings, ChunkSettingsImageScale, Clear, CreateWorld, Energy,
+    ExportChunkSettings, Food, ImportChunkSettings, Lineage, Load, LoadEnvironmentSchedule,
+    LoadObstacles, Obstacle, PaintChunkSettings, Pheromone, Position, RunTicks, Save,
+    SimulationSettings, SpawnCell, SpawnObstacle, Species, Step, TogglePause, WorldTopology,
 };
 use bevy::prelude::*;
 use bevy_egui::egui::plot::Text;
 use bevy_egui::{
     egui::{
-        plot::{Line, Plot, PlotPoints, Points},
-        CentralPanel, DragValue, Grid, Rgba, Slider, Window,
+        plot::{Line, Plot, PlotPoints, Points, Polygon},
+        Button, CentralPanel, ComboBox, DragValue, Grid, Rgba, Slider, Ui, Window,
     },
     EguiContext,
 };
+use std::{collections::BTreeMap, f32::consts::PI};
+
+/// Welcher Wert der [ChunkSettings] in der Simulation-Ansicht als Heatmap gezeichnet wird
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkSettingsOverlay {
+    #[default]
+    None,
+    SpawnChance,
+    SpawnedFoodEnergy,
+    VelocityDamping,
+}
 
 #[derive(Resource)]
 pub struct ControlCenterUi {
+    pub seed_drag_value: u64,
     pub tick_delta_seconds_slider: f32,
     pub actual_tick_delta_seconds_label: String,
+    pub ticks_per_frame_slider: u32,
+    pub frame_time_budget_seconds_slider: f32,
+    pub ticks_per_second_label: String,
+    pub run_ticks_drag_value: u64,
     pub cell_radius_drag_value: f32,
     pub food_radius_drag_value: f32,
     pub base_energy_drain_drag_value: f32,
@@ -25,8 +46,50 @@ pub struct ControlCenterUi {
     pub age_energy_drain_drag_value: f32,
     pub energy_required_for_split_drag_value: f32,
     pub child_cooldown_drag_value: u32,
+    pub sexual_reproduction_checkbox: bool,
+    pub mating_radius_drag_value: f32,
+    pub sensing_radius_drag_value: f32,
+    pub world_topology_combo_box: WorldTopology,
+    pub cell_collisions_checkbox: bool,
+    pub cell_collision_elasticity_slider: f32,
+    pub attack_drain_drag_value: f32,
+    pub attack_efficiency_slider: f32,
+    pub attack_cost_drag_value: f32,
+    pub leave_corpses_checkbox: bool,
+    pub corpse_body_energy_drag_value: f32,
+    pub vision_ray_count_slider: u32,
+    pub vision_angle_spread_slider: f32,
+    pub vision_range_drag_value: f32,
+    pub signal_channel_count_slider: u32,
+    pub signal_range_drag_value: f32,
+    pub pheromone_deposit_rate_drag_value: f32,
+    pub pheromone_diffusion_rate_slider: f32,
+    pub pheromone_decay_rate_slider: f32,
+    pub speciation_interval_drag_value: u32,
+    pub species_compatibility_threshold_drag_value: f32,
+    pub species_disjoint_coefficient_drag_value: f32,
+    pub species_weight_coefficient_drag_value: f32,
+    pub day_length_drag_value: u32,
+    pub night_food_spawn_factor_slider: f32,
+    pub night_sensing_factor_slider: f32,
+    /// Ob Zellen in der Simulation-Ansicht in der Farbe ihrer [Species] gezeichnet werden
+    pub color_cells_by_species_checkbox: bool,
+    /// Ob das [Pheromone] der Chunks in der Simulation-Ansicht als Heatmap gezeichnet wird
+    pub show_pheromones_checkbox: bool,
     pub rotation_speed_max_drag_value: f32,
     pub acceleration_max_drag_value: f32,
+    pub speed_energy_drain_drag_value: f32,
+    pub neuron_add_chance_drag_value: f32,
+    pub neuron_remove_chance_drag_value: f32,
+    pub connection_add_chance_drag_value: f32,
+    pub connection_remove_chance_drag_value: f32,
+    pub weight_distribution_combo_box: PerturbationDistribution,
+    pub weight_magnitude_drag_value: f32,
+    pub bias_distribution_combo_box: PerturbationDistribution,
+    pub bias_magnitude_drag_value: f32,
+    pub genome_distribution_combo_box: PerturbationDistribution,
+    pub genome_magnitude_drag_value: f32,
+    pub mutation_rate_adaptation_drag_value: f32,
     /// Start Energy-Wert für zukünftige manuell gespawnte Zellen
     pub cell_energy_drag_value: f32,
     pub cell_amount_slider: u32,
@@ -38,17 +101,59 @@ pub struct ControlCenterUi {
     pub velocity_damping_slider_top: f32,
     /// Wert zwischen 0 (kein Damping) und 1 (100% Damping)
     pub velocity_damping_slider_bottom: f32,
+    /// Ob Klicken und Ziehen in der Simulation-Ansicht die [ChunkSettings] mit dem Pinsel setzt
+    /// (statt Zellen auszuwählen und die Ansicht zu verschieben)
+    pub brush_checkbox: bool,
+    pub brush_radius_drag_value: f32,
+    /// Anteil zwischen 0 und 1 des Radius, über den die Wirkung des Pinsels nach außen hin abfällt
+    pub brush_falloff_slider: f32,
+    pub brush_spawn_chance_checkbox: bool,
+    pub brush_spawn_chance_drag_value: f32,
+    pub brush_food_energy_checkbox: bool,
+    pub brush_food_energy_drag_value: f32,
+    pub brush_velocity_damping_checkbox: bool,
+    pub brush_velocity_damping_slider: f32,
+    pub chunk_settings_overlay_combo_box: ChunkSettingsOverlay,
+    /// PNG, PGM oder PPM Bild für den Import und Export der [ChunkSettings]
+    pub chunk_settings_image_text_edit: String,
+    /// Spawn chance bei einem Kanal-Wert von 255
+    pub chunk_settings_image_spawn_chance_max_drag_value: f32,
+    /// Food energy bei einem Kanal-Wert von 255
+    pub chunk_settings_image_food_energy_max_drag_value: f32,
+    /// RON-Datei mit einem Environment-Schedule
+    pub environment_schedule_text_edit: String,
+    pub obstacle_circle_x_drag_value: f32,
+    pub obstacle_circle_y_drag_value: f32,
+    pub obstacle_circle_radius_drag_value: f32,
+    pub obstacle_wall_start_x_drag_value: f32,
+    pub obstacle_wall_start_y_drag_value: f32,
+    pub obstacle_wall_end_x_drag_value: f32,
+    pub obstacle_wall_end_y_drag_value: f32,
+    pub obstacle_file_text_edit: String,
+    /// Map Breite in Chunks für die nächste mit "Create world" erzeugte Welt
+    pub map_width_drag_value: u32,
+    /// Map Höhe in Chunks für die nächste mit "Create world" erzeugte Welt
+    pub map_height_drag_value: u32,
+    pub chunk_size_drag_value: f32,
     pub clear_food_checkbox: bool,
     pub clear_cells_checkbox: bool,
+    pub clear_obstacles_checkbox: bool,
+    pub clear_environment_schedule_checkbox: bool,
     pub clear_statistics_checkbox: bool,
     pub save_name_text_edit: String,
 }
 
 impl Default for ControlCenterUi {
     fn default() -> Self {
+        let mutation_settings = MutationSettings::default();
         Self {
+            seed_drag_value: 0,
             tick_delta_seconds_slider: 0.02,
             actual_tick_delta_seconds_label: "-".to_string(),
+            ticks_per_frame_slider: 1,
+            frame_time_budget_seconds_slider: 0.03,
+            ticks_per_second_label: "-".to_string(),
+            run_ticks_drag_value: 100,
             cell_radius_drag_value: 5.,
             food_radius_drag_value: 3.,
             base_energy_drain_drag_value: 0.4,
@@ -57,8 +162,48 @@ impl Default for ControlCenterUi {
             age_energy_drain_drag_value: 0.00008,
             energy_required_for_split_drag_value: 10.,
             child_cooldown_drag_value: 10,
+            sexual_reproduction_checkbox: false,
+            mating_radius_drag_value: 15.,
+            sensing_radius_drag_value: 50.,
+            world_topology_combo_box: WorldTopology::default(),
+            cell_collisions_checkbox: true,
+            cell_collision_elasticity_slider: 0.5,
+            attack_drain_drag_value: 2.,
+            attack_efficiency_slider: 0.5,
+            attack_cost_drag_value: 0.05,
+            leave_corpses_checkbox: false,
+            corpse_body_energy_drag_value: 1.,
+            vision_ray_count_slider: 3,
+            vision_angle_spread_slider: PI / 2.,
+            vision_range_drag_value: 100.,
+            signal_channel_count_slider: 2,
+            signal_range_drag_value: 50.,
+            pheromone_deposit_rate_drag_value: 1.,
+            pheromone_diffusion_rate_slider: 0.1,
+            pheromone_decay_rate_slider: 0.01,
+            speciation_interval_drag_value: 50,
+            species_compatibility_threshold_drag_value: 0.6,
+            species_disjoint_coefficient_drag_value: 1.,
+            species_weight_coefficient_drag_value: 0.4,
+            day_length_drag_value: 0,
+            night_food_spawn_factor_slider: 0.5,
+            night_sensing_factor_slider: 0.5,
+            color_cells_by_species_checkbox: false,
+            show_pheromones_checkbox: false,
             rotation_speed_max_drag_value: 1.,
             acceleration_max_drag_value: 1.7,
+            speed_energy_drain_drag_value: 0.05,
+            neuron_add_chance_drag_value: mutation_settings.neuron_add_chance,
+            neuron_remove_chance_drag_value: mutation_settings.neuron_remove_chance,
+            connection_add_chance_drag_value: mutation_settings.connection_add_chance,
+            connection_remove_chance_drag_value: mutation_settings.connection_remove_chance,
+            weight_distribution_combo_box: mutation_settings.weight_distribution,
+            weight_magnitude_drag_value: mutation_settings.weight_magnitude,
+            bias_distribution_combo_box: mutation_settings.bias_distribution,
+            bias_magnitude_drag_value: mutation_settings.bias_magnitude,
+            genome_distribution_combo_box: mutation_settings.genome_distribution,
+            genome_magnitude_drag_value: mutation_settings.genome_magnitude,
+            mutation_rate_adaptation_drag_value: mutation_settings.mutation_rate_adaptation,
             cell_energy_drag_value: 199.,
             cell_amount_slider: 50,
             velocity_damping_slider_bottom: 0.4,
@@ -66,8 +211,37 @@ impl Default for ControlCenterUi {
             food_energy_drag_value: 200.,
             food_spawn_chance_slider_left: 0.018,
             food_spawn_chance_slider_right: 0.018,
+            brush_checkbox: false,
+            brush_radius_drag_value: 100.,
+            brush_falloff_slider: 0.5,
+            brush_spawn_chance_checkbox: true,
+            brush_spawn_chance_drag_value: 0.1,
+            brush_food_energy_checkbox: false,
+            brush_food_energy_drag_value: 200.,
+            brush_velocity_damping_checkbox: false,
+            brush_velocity_damping_slider: 0.4,
+            chunk_settings_overlay_combo_box: ChunkSettingsOverlay::None,
+            chunk_settings_image_text_edit: "assets/chunk_settings.png".to_string(),
+            chunk_settings_image_spawn_chance_max_drag_value: ChunkSettingsImageScale::default()
+                .spawn_chance_max,
+            chunk_settings_image_food_energy_max_drag_value: ChunkSettingsImageScale::default()
+                .spawned_food_energy_max,
+            environment_schedule_text_edit: "assets/environment_schedule.ron".to_string(),
+            obstacle_circle_x_drag_value: 1000.,
+            obstacle_circle_y_drag_value: 1000.,
+            obstacle_circle_radius_drag_value: 100.,
+            obstacle_wall_start_x_drag_value: 500.,
+            obstacle_wall_start_y_drag_value: 1000.,
+            obstacle_wall_end_x_drag_value: 1500.,
+            obstacle_wall_end_y_drag_value: 1000.,
+            obstacle_file_text_edit: "assets/obstacles.ron".to_string(),
+            map_width_drag_value: 40,
+            map_height_drag_value: 40,
+            chunk_size_drag_value: 50.,
             clear_food_checkbox: true,
             clear_cells_checkbox: true,
+            clear_obstacles_checkbox: false,
+            clear_environment_schedule_checkbox: false,
             clear_statistics_checkbox: false,
             save_name_text_edit: "save".to_string(),
         }
@@ -78,9 +252,28 @@ pub fn display_control_center(
     mut egui_context: ResMut<EguiContext>,
     mut control_center_ui: ResMut<ControlCenterUi>,
     mut spawn_cell_events: EventWriter<SpawnCell>,
-    mut apply_chunk_settings_events: EventWriter<ApplyChunkSettings>,
+    // Bevy Systeme haben höchstens 16 Parameter, deshalb sind die Events der Map zusammengefasst
+    (mut spawn_obstacle_events, mut load_obstacles_events, mut create_world_events): (
+        EventWriter<SpawnObstacle>,
+        EventWriter<LoadObstacles>,
+        EventWriter<CreateWorld>,
+    ),
+    (
+        mut apply_chunk_settings_events,
+        mut import_chunk_settings_events,
+        mut export_chunk_settings_events,
+        mut load_environment_schedule_events,
+    ): (
+        EventWriter<ApplyChunkSettings>,
+        EventWriter<ImportChunkSettings>,
+        EventWriter<ExportChunkSettings>,
+        EventWriter<LoadEnvironmentSchedule>,
+    ),
     mut apply_simulation_settings_events: EventWriter<ApplySimulationSettings>,
+    mut apply_mutation_settings_events: EventWriter<ApplyMutationSettings>,
     mut toggle_pause_events: EventWriter<TogglePause>,
+    mut step_events: EventWriter<Step>,
+    mut run_ticks_events: EventWriter<RunTicks>,
     mut clear_events: EventWriter<Clear>,
     mut save_events: EventWriter<Save>,
     mut load_events: EventWriter<Load>,
@@ -106,6 +299,21 @@ pub fn display_control_center(
                         &control_center_ui.actual_tick_delta_seconds_label,
                     );
                     grid_ui.end_row();
+                    grid_ui.label("Ticks per frame: ");
+                    grid_ui.add(
+                        Slider::new(&mut control_center_ui.ticks_per_frame_slider, 1..=10000)
+                            .logarithmic(true),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Frame time budget seconds: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.frame_time_budget_seconds_slider,
+                        0.0..=0.2,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Ticks per second: ");
+                    grid_ui.colored_label(Rgba::WHITE, &control_center_ui.ticks_per_second_label);
+                    grid_ui.end_row();
                     grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Cells -");
                     grid_ui.end_row();
                     grid_ui.label("Base energy drain: ");
@@ -144,6 +352,136 @@ pub fn display_control_center(
                         0..=50,
                     ));
                     grid_ui.end_row();
+                    grid_ui.label("Sexual reproduction: ");
+                    grid_ui.checkbox(&mut control_center_ui.sexual_reproduction_checkbox, "");
+                    grid_ui.end_row();
+                    grid_ui.label("Mating radius: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.mating_radius_drag_value).speed(0.1),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Sensing radius: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.sensing_radius_drag_value)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("World topology: ");
+                    ComboBox::from_id_source("world_topology_combo_box")
+                        .selected_text(match control_center_ui.world_topology_combo_box {
+                            WorldTopology::BoundedLethal => "Bounded (lethal)",
+                            WorldTopology::BoundedReflective => "Bounded (reflective)",
+                            WorldTopology::Torus => "Torus",
+                        })
+                        .show_ui(grid_ui, |combo_box_ui| {
+                            combo_box_ui.selectable_value(
+                                &mut control_center_ui.world_topology_combo_box,
+                                WorldTopology::BoundedLethal,
+                                "Bounded (lethal)",
+                            );
+                            combo_box_ui.selectable_value(
+                                &mut control_center_ui.world_topology_combo_box,
+                                WorldTopology::BoundedReflective,
+                                "Bounded (reflective)",
+                            );
+                            combo_box_ui.selectable_value(
+                                &mut control_center_ui.world_topology_combo_box,
+                                WorldTopology::Torus,
+                                "Torus",
+                            );
+                        });
+                    grid_ui.end_row();
+                    grid_ui.label("Cell collisions: ");
+                    grid_ui.checkbox(&mut control_center_ui.cell_collisions_checkbox, "");
+                    grid_ui.end_row();
+                    grid_ui.label("Collision elasticity: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.cell_collision_elasticity_slider,
+                        0.0..=1.,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Attack drain: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.attack_drain_drag_value)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Attack efficiency: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.attack_efficiency_slider,
+                        0.0..=1.,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Attack cost: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.attack_cost_drag_value)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Leave corpses: ");
+                    grid_ui.checkbox(&mut control_center_ui.leave_corpses_checkbox, "");
+                    grid_ui.end_row();
+                    grid_ui.label("Corpse body energy: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.corpse_body_energy_drag_value)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Vision rays: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.vision_ray_count_slider,
+                        0..=VISION_RAY_COUNT_MAX as u32,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Vision angle spread: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.vision_angle_spread_slider,
+                        0.0..=2. * PI,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Vision range: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.vision_range_drag_value)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Signal channels: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.signal_channel_count_slider,
+                        0..=SIGNAL_CHANNEL_COUNT_MAX as u32,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Signal range: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.signal_range_drag_value)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Pheromone deposit rate: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.pheromone_deposit_rate_drag_value)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Pheromone diffusion rate: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.pheromone_diffusion_rate_slider,
+                        0.0..=1.,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Pheromone decay rate: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.pheromone_decay_rate_slider,
+                        0.0..=1.,
+                    ));
+                    grid_ui.end_row();
                     grid_ui.label("Rotation speed max.: ");
                     grid_ui.add(
                         DragValue::new(&mut control_center_ui.rotation_speed_max_drag_value)
@@ -156,13 +494,83 @@ pub fn display_control_center(
                             .speed(0.01),
                     );
                     grid_ui.end_row();
+                    grid_ui.label("Speed energy drain: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.speed_energy_drain_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
                     grid_ui.label("Cell radius: ");
                     grid_ui.add(
                         DragValue::new(&mut control_center_ui.cell_radius_drag_value).speed(0.01),
                     );
                     grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Day & night -");
+                    grid_ui.end_row();
+                    grid_ui.label("Day length (0 = always day): ");
+                    grid_ui.add(DragValue::new(&mut control_center_ui.day_length_drag_value));
+                    grid_ui.end_row();
+                    grid_ui.label("Night food spawn factor: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.night_food_spawn_factor_slider,
+                        0.0..=1.0,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Night sensing factor: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.night_sensing_factor_slider,
+                        0.0..=1.0,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Species -");
+                    grid_ui.end_row();
+                    grid_ui.label("Speciation interval: ");
+                    grid_ui.add(DragValue::new(
+                        &mut control_center_ui.speciation_interval_drag_value,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Compatibility threshold: ");
+                    grid_ui.add(
+                        DragValue::new(
+                            &mut control_center_ui.species_compatibility_threshold_drag_value,
+                        )
+                        .speed(0.01)
+                        .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Disjoint coefficient: ");
+                    grid_ui.add(
+                        DragValue::new(
+                            &mut control_center_ui.species_disjoint_coefficient_drag_value,
+                        )
+                        .speed(0.01),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Weight coefficient: ");
+                    grid_ui.add(
+                        DragValue::new(
+                            &mut control_center_ui.species_weight_coefficient_drag_value,
+                        )
+                        .speed(0.01),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Color cells by species: ");
+                    grid_ui.checkbox(&mut control_center_ui.color_cells_by_species_checkbox, "");
+                    grid_ui.end_row();
+                    grid_ui.label("Show pheromones: ");
+                    grid_ui.checkbox(&mut control_center_ui.show_pheromones_checkbox, "");
+                    grid_ui.end_row();
                     grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Miscellaneous -");
                     grid_ui.end_row();
+                    grid_ui.label("Seed: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(DragValue::new(&mut control_center_ui.seed_drag_value));
+                        if cell_ui.button("Random").clicked() {
+                            control_center_ui.seed_drag_value = rand::random();
+                        }
+                    });
+                    grid_ui.end_row();
                     grid_ui.label("Food radius: ");
                     grid_ui.add(
                         DragValue::new(&mut control_center_ui.food_radius_drag_value).speed(0.01),
@@ -182,8 +590,114 @@ pub fn display_control_center(
                         {
                             toggle_pause_events.send(TogglePause);
                         }
+                        if cell_ui
+                            .add_enabled(simulation_settings.is_paused, Button::new("Step"))
+                            .clicked()
+                        {
+                            step_events.send(Step);
+                        }
                     });
                     grid_ui.end_row();
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(
+                            DragValue::new(&mut control_center_ui.run_ticks_drag_value)
+                                .clamp_range(1..=u64::MAX),
+                        );
+                        if cell_ui.button("Run ticks").clicked() {
+                            run_ticks_events.send(RunTicks {
+                                tick_count: control_center_ui.run_ticks_drag_value,
+                            });
+                        }
+                    });
+                    grid_ui.end_row();
+                });
+            });
+            ui.collapsing("Mutation Settings", |collapsing_ui| {
+                Grid::new("mutation_settings_grid").show(collapsing_ui, |grid_ui| {
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Structure -");
+                    grid_ui.end_row();
+                    grid_ui.label("Neuron add chance: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.neuron_add_chance_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Neuron remove chance: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.neuron_remove_chance_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Connection add chance: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.connection_add_chance_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Connection remove chance: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.connection_remove_chance_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Weights -");
+                    grid_ui.end_row();
+                    grid_ui.label("Weight distribution: ");
+                    perturbation_distribution_combo_box(
+                        grid_ui,
+                        "weight_distribution_combo_box",
+                        &mut control_center_ui.weight_distribution_combo_box,
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Weight magnitude: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.weight_magnitude_drag_value)
+                            .speed(0.01),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Bias distribution: ");
+                    perturbation_distribution_combo_box(
+                        grid_ui,
+                        "bias_distribution_combo_box",
+                        &mut control_center_ui.bias_distribution_combo_box,
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Bias magnitude: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.bias_magnitude_drag_value)
+                            .speed(0.01),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Genome -");
+                    grid_ui.end_row();
+                    grid_ui.label("Genome distribution: ");
+                    perturbation_distribution_combo_box(
+                        grid_ui,
+                        "genome_distribution_combo_box",
+                        &mut control_center_ui.genome_distribution_combo_box,
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Genome magnitude: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.genome_magnitude_drag_value)
+                            .speed(0.001),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Mutation rate adaptation: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.mutation_rate_adaptation_drag_value)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    if grid_ui.button("Apply").clicked() {
+                        apply_mutation_settings_events.send(ApplyMutationSettings);
+                    }
+                    grid_ui.end_row();
                 });
             });
             ui.collapsing("Spawn Cells", |collapsing_ui| {
@@ -209,6 +723,129 @@ pub fn display_control_center(
                     grid_ui.end_row();
                 });
             });
+            ui.collapsing("Obstacles", |collapsing_ui| {
+                Grid::new("obstacles_grid").show(collapsing_ui, |grid_ui| {
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Circle -");
+                    grid_ui.end_row();
+                    grid_ui.label("Center: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_circle_x_drag_value,
+                        ));
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_circle_y_drag_value,
+                        ));
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("Radius: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.obstacle_circle_radius_drag_value)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    if grid_ui.button("Add circle").clicked() {
+                        spawn_obstacle_events.send(SpawnObstacle {
+                            obstacle: Obstacle::Circle {
+                                center: Position {
+                                    x: control_center_ui.obstacle_circle_x_drag_value,
+                                    y: control_center_ui.obstacle_circle_y_drag_value,
+                                },
+                                radius: control_center_ui.obstacle_circle_radius_drag_value,
+                            },
+                        });
+                    }
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Wall -");
+                    grid_ui.end_row();
+                    grid_ui.label("Start: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_wall_start_x_drag_value,
+                        ));
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_wall_start_y_drag_value,
+                        ));
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("End: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_wall_end_x_drag_value,
+                        ));
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.obstacle_wall_end_y_drag_value,
+                        ));
+                    });
+                    grid_ui.end_row();
+                    if grid_ui.button("Add wall").clicked() {
+                        spawn_obstacle_events.send(SpawnObstacle {
+                            obstacle: Obstacle::Segment {
+                                start: Position {
+                                    x: control_center_ui.obstacle_wall_start_x_drag_value,
+                                    y: control_center_ui.obstacle_wall_start_y_drag_value,
+                                },
+                                end: Position {
+                                    x: control_center_ui.obstacle_wall_end_x_drag_value,
+                                    y: control_center_ui.obstacle_wall_end_y_drag_value,
+                                },
+                            },
+                        });
+                    }
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- File -");
+                    grid_ui.end_row();
+                    grid_ui.label("Obstacle file: ");
+                    grid_ui.text_edit_singleline(&mut control_center_ui.obstacle_file_text_edit);
+                    grid_ui.end_row();
+                    if grid_ui.button("Load").clicked() {
+                        load_obstacles_events.send(LoadObstacles {
+                            path: control_center_ui.obstacle_file_text_edit.clone(),
+                        });
+                    }
+                    grid_ui.end_row();
+                });
+            });
+            ui.collapsing("World", |collapsing_ui| {
+                Grid::new("world_grid").show(collapsing_ui, |grid_ui| {
+                    grid_ui.label("Current map size: ");
+                    grid_ui.colored_label(
+                        Rgba::WHITE,
+                        format!(
+                            "{} x {} chunks of {}",
+                            simulation_settings.map_width,
+                            simulation_settings.map_height,
+                            simulation_settings.chunk_size
+                        ),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Map size (chunks): ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.add(
+                            DragValue::new(&mut control_center_ui.map_width_drag_value)
+                                .clamp_range(1..=500),
+                        );
+                        cell_ui.add(
+                            DragValue::new(&mut control_center_ui.map_height_drag_value)
+                                .clamp_range(1..=500),
+                        );
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("Chunk size: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.chunk_size_drag_value)
+                            .clamp_range(1.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    if grid_ui.button("Create world").clicked() {
+                        create_world_events.send(CreateWorld {
+                            map_width: control_center_ui.map_width_drag_value,
+                            map_height: control_center_ui.map_height_drag_value,
+                            chunk_size: control_center_ui.chunk_size_drag_value,
+                        });
+                    }
+                    grid_ui.end_row();
+                });
+            });
             ui.collapsing("Chunk Settings", |collapsing_ui| {
                 Grid::new("chunk_settings_grid").show(collapsing_ui, |grid_ui| {
                     grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Cells -");
@@ -248,11 +885,142 @@ pub fn display_control_center(
                         apply_chunk_settings_events.send(ApplyChunkSettings);
                     }
                     grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Brush -");
+                    grid_ui.end_row();
+                    grid_ui.label("Paint on map: ");
+                    grid_ui.checkbox(&mut control_center_ui.brush_checkbox, "");
+                    grid_ui.end_row();
+                    grid_ui.label("Radius: ");
+                    grid_ui.add(
+                        DragValue::new(&mut control_center_ui.brush_radius_drag_value)
+                            .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Falloff: ");
+                    grid_ui.add(Slider::new(
+                        &mut control_center_ui.brush_falloff_slider,
+                        0.0..=1.0,
+                    ));
+                    grid_ui.end_row();
+                    grid_ui.label("Spawn chance: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.checkbox(&mut control_center_ui.brush_spawn_chance_checkbox, "");
+                        cell_ui.add(
+                            DragValue::new(&mut control_center_ui.brush_spawn_chance_drag_value)
+                                .speed(0.001)
+                                .clamp_range(0.0..=f32::MAX),
+                        );
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("Food energy: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui.checkbox(&mut control_center_ui.brush_food_energy_checkbox, "");
+                        cell_ui.add(DragValue::new(
+                            &mut control_center_ui.brush_food_energy_drag_value,
+                        ));
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("Velocity damping: ");
+                    grid_ui.horizontal(|cell_ui| {
+                        cell_ui
+                            .checkbox(&mut control_center_ui.brush_velocity_damping_checkbox, "");
+                        cell_ui.add(Slider::new(
+                            &mut control_center_ui.brush_velocity_damping_slider,
+                            0.0..=1.0,
+                        ));
+                    });
+                    grid_ui.end_row();
+                    grid_ui.label("Overlay: ");
+                    ComboBox::from_id_source("chunk_settings_overlay_combo_box")
+                        .selected_text(chunk_settings_overlay_label(
+                            control_center_ui.chunk_settings_overlay_combo_box,
+                        ))
+                        .show_ui(grid_ui, |combo_box_ui| {
+                            for chunk_settings_overlay in [
+                                ChunkSettingsOverlay::None,
+                                ChunkSettingsOverlay::SpawnChance,
+                                ChunkSettingsOverlay::SpawnedFoodEnergy,
+                                ChunkSettingsOverlay::VelocityDamping,
+                            ] {
+                                combo_box_ui.selectable_value(
+                                    &mut control_center_ui.chunk_settings_overlay_combo_box,
+                                    chunk_settings_overlay,
+                                    chunk_settings_overlay_label(chunk_settings_overlay),
+                                );
+                            }
+                        });
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Image -");
+                    grid_ui.end_row();
+                    grid_ui.label("Image file: ");
+                    grid_ui.text_edit_singleline(
+                        &mut control_center_ui.chunk_settings_image_text_edit,
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Spawn chance max (red): ");
+                    grid_ui.add(
+                        DragValue::new(
+                            &mut control_center_ui.chunk_settings_image_spawn_chance_max_drag_value,
+                        )
+                        .speed(0.001)
+                        .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    grid_ui.label("Food energy max (green): ");
+                    grid_ui.add(
+                        DragValue::new(
+                            &mut control_center_ui.chunk_settings_image_food_energy_max_drag_value,
+                        )
+                        .clamp_range(0.0..=f32::MAX),
+                    );
+                    grid_ui.end_row();
+                    let chunk_settings_image_scale = ChunkSettingsImageScale {
+                        spawn_chance_max: control_center_ui
+                            .chunk_settings_image_spawn_chance_max_drag_value,
+                        spawned_food_energy_max: control_center_ui
+                            .chunk_settings_image_food_energy_max_drag_value,
+                    };
+                    grid_ui.horizontal(|cell_ui| {
+                        if cell_ui.button("Import").clicked() {
+                            import_chunk_settings_events.send(ImportChunkSettings {
+                                path: control_center_ui.chunk_settings_image_text_edit.clone(),
+                                scale: chunk_settings_image_scale,
+                            });
+                        }
+                        if cell_ui.button("Export").clicked() {
+                            export_chunk_settings_events.send(ExportChunkSettings {
+                                path: control_center_ui.chunk_settings_image_text_edit.clone(),
+                                scale: chunk_settings_image_scale,
+                            });
+                        }
+                    });
+                    grid_ui.end_row();
+                    grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Environment -");
+                    grid_ui.end_row();
+                    grid_ui.label("Schedule file: ");
+                    grid_ui.text_edit_singleline(
+                        &mut control_center_ui.environment_schedule_text_edit,
+                    );
+                    grid_ui.end_row();
+                    if grid_ui.button("Load").clicked() {
+                        load_environment_schedule_events.send(LoadEnvironmentSchedule {
+                            path: control_center_ui.environment_schedule_text_edit.clone(),
+                        });
+                    }
+                    grid_ui.end_row();
                 });
             });
             ui.collapsing("Destroy", |collapsing_ui| {
                 collapsing_ui.checkbox(&mut control_center_ui.clear_food_checkbox, "Clear food");
                 collapsing_ui.checkbox(&mut control_center_ui.clear_cells_checkbox, "Clear cells");
+                collapsing_ui.checkbox(
+                    &mut control_center_ui.clear_obstacles_checkbox,
+                    "Clear obstacles",
+                );
+                collapsing_ui.checkbox(
+                    &mut control_center_ui.clear_environment_schedule_checkbox,
+                    "Clear environment schedule",
+                );
                 collapsing_ui.checkbox(
                     &mut control_center_ui.clear_statistics_checkbox,
                     "Clear statistics",
@@ -261,6 +1029,9 @@ pub fn display_control_center(
                     clear_events.send(Clear {
                         clear_food: control_center_ui.clear_food_checkbox,
                         clear_cells: control_center_ui.clear_cells_checkbox,
+                        clear_obstacles: control_center_ui.clear_obstacles_checkbox,
+                        clear_environment_schedule: control_center_ui
+                            .clear_environment_schedule_checkbox,
                         clear_statistics: control_center_ui.clear_statistics_checkbox,
                     });
                 }
@@ -285,25 +1056,177 @@ pub fn display_control_center(
             });
             ui.collapsing("Statistics", |collapsing_ui| {
                 for (label, mut is_open) in &mut statistic_query {
-                    collapsing_ui.checkbox(&mut **is_open, label.as_str());
+                    collapsing_ui.checkbox(&mut is_open, label.as_str());
                 }
             });
         });
 }
 
+/// Auswahl einer [PerturbationDistribution] (mit Exponent bei `Power`)
+fn perturbation_distribution_combo_box(
+    ui: &mut Ui,
+    id_source: &str,
+    distribution: &mut PerturbationDistribution,
+) {
+    ui.horizontal(|cell_ui| {
+        ComboBox::from_id_source(id_source)
+            .selected_text(match distribution {
+                PerturbationDistribution::Uniform => "Uniform",
+                PerturbationDistribution::Normal => "Normal",
+                PerturbationDistribution::Power(_) => "Power",
+            })
+            .show_ui(cell_ui, |combo_box_ui| {
+                combo_box_ui.selectable_value(
+                    distribution,
+                    PerturbationDistribution::Uniform,
+                    "Uniform",
+                );
+                combo_box_ui.selectable_value(
+                    distribution,
+                    PerturbationDistribution::Normal,
+                    "Normal",
+                );
+                if combo_box_ui
+                    .selectable_label(
+                        matches!(distribution, PerturbationDistribution::Power(_)),
+                        "Power",
+                    )
+                    .clicked()
+                    && !matches!(distribution, PerturbationDistribution::Power(_))
+                {
+                    *distribution = PerturbationDistribution::Power(25);
+                }
+            });
+        if let PerturbationDistribution::Power(exponent) = distribution {
+            cell_ui.label("Exponent: ");
+            cell_ui.add(DragValue::new(exponent).clamp_range(1..=99));
+        }
+    });
+}
+
+/// Name eines [ChunkSettingsOverlay] in der Ui
+fn chunk_settings_overlay_label(chunk_settings_overlay: ChunkSettingsOverlay) -> &'static str {
+    match chunk_settings_overlay {
+        ChunkSettingsOverlay::None => "None",
+        ChunkSettingsOverlay::SpawnChance => "Spawn chance",
+        ChunkSettingsOverlay::SpawnedFoodEnergy => "Food energy",
+        ChunkSettingsOverlay::VelocityDamping => "Velocity damping",
+    }
+}
+
 pub fn display_simulation(
     mut egui_context: ResMut<EguiContext>,
     mut cell_inspector_ui: ResMut<CellInspectorUi>,
+    mut paint_chunk_settings_events: EventWriter<PaintChunkSettings>,
     simulation_settings: Res<SimulationSettings>,
-    cell_query: Query<(Entity, &Position, &Energy), With<Cell>>,
+    control_center_ui: Res<ControlCenterUi>,
+    cell_query: Query<(Entity, &Position, &Energy, &Species, &Genome), With<Cell>>,
     food_query: Query<&Position, With<Food>>,
+    chunk_query: Query<(&Position, &Pheromone, &ChunkSettings), With<Chunk>>,
+    chunk_registry_query: Query<&ChunkRegistry>,
+    obstacle_query: Query<&Obstacle>,
+    time: Res<Time>,
 ) {
     CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
         Plot::new("simulation_plot")
             .data_aspect(1.)
             .view_aspect(1.)
             .legend(default())
+            // Beim Malen mit dem Pinsel wird die Ansicht nicht verschoben
+            .allow_drag(!control_center_ui.brush_checkbox)
             .show(ui, |plot_ui| {
+                // Chunk Werte als Heatmap zeichnen (relativ zum höchsten Wert, fast leere Chunks werden ausgelassen)
+                let half_chunk_size = chunk_registry_query
+                    .get_single()
+                    .map_or(0., |chunk_registry| chunk_registry.chunk_size as f64 / 2.);
+                let mut draw_heatmap = |value: &dyn Fn(&Pheromone, &ChunkSettings) -> f32,
+                                        value_max: Option<f32>,
+                                        color: Rgba,
+                                        name: &str| {
+                    let value_max = value_max.unwrap_or_else(|| {
+                        chunk_query.iter().fold(
+                            0.,
+                            |value_max: f32, (_, pheromone, chunk_settings)| {
+                                value_max.max(value(pheromone, chunk_settings))
+                            },
+                        )
+                    });
+                    for (position, pheromone, chunk_settings) in &chunk_query {
+                        let intensity = value(pheromone, chunk_settings) / value_max;
+                        if value_max <= 0. || intensity < 0.02 {
+                            continue;
+                        }
+                        let (x, y) = (position.x as f64, position.y as f64);
+                        plot_ui.polygon(
+                            Polygon::new(PlotPoints::new(vec![
+                                [x - half_chunk_size, y - half_chunk_size],
+                                [x + half_chunk_size, y - half_chunk_size],
+                                [x + half_chunk_size, y + half_chunk_size],
+                                [x - half_chunk_size, y + half_chunk_size],
+                            ]))
+                            .width(0.)
+                            .color(color)
+                            .fill_alpha(intensity.min(1.) * 0.6)
+                            .name(name),
+                        );
+                    }
+                };
+                match control_center_ui.chunk_settings_overlay_combo_box {
+                    ChunkSettingsOverlay::None => {}
+                    ChunkSettingsOverlay::SpawnChance => draw_heatmap(
+                        &|_, chunk_settings| chunk_settings.spawn_chance,
+                        None,
+                        Rgba::from_rgb(0.145, 0.569, 0.129),
+                        "Spawn chance",
+                    ),
+                    ChunkSettingsOverlay::SpawnedFoodEnergy => draw_heatmap(
+                        &|_, chunk_settings| chunk_settings.spawned_food_energy,
+                        None,
+                        Rgba::from_rgb(0.569, 0.569, 0.129),
+                        "Food energy",
+                    ),
+                    // Velocity damping liegt immer zwischen 0 und 1
+                    ChunkSettingsOverlay::VelocityDamping => draw_heatmap(
+                        &|_, chunk_settings| chunk_settings.velocity_damping,
+                        Some(1.),
+                        Rgba::from_rgb(0.129, 0.145, 0.569),
+                        "Velocity damping",
+                    ),
+                }
+                if control_center_ui.show_pheromones_checkbox {
+                    draw_heatmap(
+                        &|pheromone, _| **pheromone,
+                        None,
+                        Rgba::from_rgb(0.569, 0.129, 0.569),
+                        "Pheromone",
+                    );
+                }
+
+                // Hindernisse zeichnen (Kreise als Vieleck)
+                for obstacle in &obstacle_query {
+                    let points: Vec<[f64; 2]> = match obstacle {
+                        Obstacle::Circle { center, radius } => (0..=32)
+                            .map(|index| {
+                                let angle = index as f32 / 32. * 2. * PI;
+                                [
+                                    (center.x + angle.cos() * radius) as f64,
+                                    (center.y + angle.sin() * radius) as f64,
+                                ]
+                            })
+                            .collect(),
+                        Obstacle::Segment { start, end } => vec![
+                            [start.x as f64, start.y as f64],
+                            [end.x as f64, end.y as f64],
+                        ],
+                    };
+                    plot_ui.line(
+                        Line::new(PlotPoints::new(points))
+                            .width(2.)
+                            .color(Rgba::from_gray(0.6))
+                            .name("Obstacle"),
+                    );
+                }
+
                 // Nahrung Daten sammeln
                 let mut food_points = Vec::new();
                 for position in &food_query {
@@ -318,64 +1241,155 @@ pub fn display_simulation(
                         .name("Food"),
                 );
 
-                // Zellen Daten sammeln
-                let mut cell_point_groups = vec![
-                    Vec::new(),
-                    Vec::new(),
-                    Vec::new(),
-                    Vec::new(),
-                    Vec::new(),
-                    Vec::new(),
-                ];
-                let group_colors = [
-                    Rgba::from_gray(0.8),
-                    Rgba::from_rgb(0.569, 0.129, 0.145) * 0.2,
-                    Rgba::from_rgb(0.569, 0.129, 0.145) * 0.4,
-                    Rgba::from_rgb(0.569, 0.129, 0.145) * 0.6,
-                    Rgba::from_rgb(0.569, 0.129, 0.145) * 0.8,
-                    Rgba::from_rgb(0.569, 0.129, 0.145),
-                ];
-                let group_labels = [
-                    "Selected cell",
-                    "Cell < 50 energy",
-                    "Cell < 100 energy",
-                    "Cell < 200 energy",
-                    "Cell < 400 energy",
-                    "Cell > 400 energy",
-                ];
-                for (entity, position, energy) in &cell_query {
-                    let group = if Some(entity) == cell_inspector_ui.selected_cell {
-                        0
-                    } else if **energy < 50. {
-                        1
-                    } else if **energy < 100. {
-                        2
-                    } else if **energy < 200. {
-                        3
-                    } else if **energy < 400. {
-                        4
-                    } else {
-                        5
-                    };
-                    cell_point_groups[group].push([position.x as f64, position.y as f64]);
-                }
+                // Zellen werden zusätzlich nach Radius (auf 0.5 gerundet) gruppiert,
+                // da alle Punkte einer Gruppe mit dem gleichen Radius gezeichnet werden
+                let radius_key =
+                    |genome: &Genome| (genome.radius(&simulation_settings) * 2.).round() as u32;
+
+                if control_center_ui.color_cells_by_species_checkbox {
+                    // Zellen Daten nach Species sammeln
+                    let mut selected_cell_points = Vec::new();
+                    let mut selected_cell_radius = simulation_settings.cell_radius;
+                    let mut species_point_groups = BTreeMap::new();
+                    for (entity, position, _, species, genome) in &cell_query {
+                        let point = [position.x as f64, position.y as f64];
+                        if Some(entity) == cell_inspector_ui.selected_cell {
+                            selected_cell_points.push(point);
+                            selected_cell_radius = genome.radius(&simulation_settings);
+                        } else {
+                            species_point_groups
+                                .entry((species.id, radius_key(genome)))
+                                .or_insert((species.color, Vec::new()))
+                                .1
+                                .push(point);
+                        }
+                    }
 
-                // Zellen zeichnen
-                for index in 0..cell_point_groups.len() {
+                    // Zellen zeichnen
                     plot_ui.points(
-                        Points::new(PlotPoints::new(cell_point_groups[index].clone()))
-                            .radius(simulation_settings.cell_radius)
-                            .color(group_colors[index])
-                            .name(group_labels[index]),
+                        Points::new(PlotPoints::new(selected_cell_points))
+                            .radius(selected_cell_radius)
+                            .color(Rgba::from_gray(0.8))
+                            .name("Selected cell"),
                     );
+                    for ((species_id, radius_key), (color, points)) in species_point_groups {
+                        // Id 0: Zellen, die noch keiner Species zugeordnet wurden
+                        plot_ui.points(
+                            Points::new(PlotPoints::new(points))
+                                .radius(radius_key as f32 / 2.)
+                                .color(if species_id == 0 {
+                                    Rgba::from_gray(0.4)
+                                } else {
+                                    Rgba::from_rgb(color[0], color[1], color[2])
+                                })
+                                .name(if species_id == 0 {
+                                    "No species".to_string()
+                                } else {
+                                    format!("Species {}", species_id)
+                                }),
+                        );
+                    }
+                } else {
+                    // Zellen Daten sammeln
+                    let mut cell_point_groups = BTreeMap::new();
+                    let group_colors = [
+                        Rgba::from_gray(0.8),
+                        Rgba::from_rgb(0.569, 0.129, 0.145) * 0.2,
+                        Rgba::from_rgb(0.569, 0.129, 0.145) * 0.4,
+                        Rgba::from_rgb(0.569, 0.129, 0.145) * 0.6,
+                        Rgba::from_rgb(0.569, 0.129, 0.145) * 0.8,
+                        Rgba::from_rgb(0.569, 0.129, 0.145),
+                    ];
+                    let group_labels = [
+                        "Selected cell",
+                        "Cell < 50 energy",
+                        "Cell < 100 energy",
+                        "Cell < 200 energy",
+                        "Cell < 400 energy",
+                        "Cell > 400 energy",
+                    ];
+                    for (entity, position, energy, _, genome) in &cell_query {
+                        let group = if Some(entity) == cell_inspector_ui.selected_cell {
+                            0
+                        } else if **energy < 50. {
+                            1
+                        } else if **energy < 100. {
+                            2
+                        } else if **energy < 200. {
+                            3
+                        } else if **energy < 400. {
+                            4
+                        } else {
+                            5
+                        };
+                        cell_point_groups
+                            .entry((group, radius_key(genome)))
+                            .or_insert_with(Vec::new)
+                            .push([position.x as f64, position.y as f64]);
+                    }
+
+                    // Zellen zeichnen
+                    for ((group, radius_key), points) in cell_point_groups {
+                        plot_ui.points(
+                            Points::new(PlotPoints::new(points))
+                                .radius(radius_key as f32 / 2.)
+                                .color(group_colors[group])
+                                .name(group_labels[group]),
+                        );
+                    }
                 }
 
-                if plot_ui.plot_clicked() {
+                if control_center_ui.brush_checkbox {
+                    // Pinsel am Cursor zeichnen und malen, solange die linke Maustaste gedrückt ist
+                    let pointer = plot_ui.ctx().input().pointer.clone();
+                    if let Some(pointer_position) =
+                        pointer.hover_pos().filter(|_| plot_ui.plot_hovered())
+                    {
+                        let cursor_position = plot_ui.plot_from_screen(pointer_position);
+                        let center = Position {
+                            x: cursor_position.x as f32,
+                            y: cursor_position.y as f32,
+                        };
+                        let radius = control_center_ui.brush_radius_drag_value;
+                        plot_ui.line(
+                            Line::new(PlotPoints::new(
+                                (0..=32)
+                                    .map(|index| {
+                                        let angle = index as f32 / 32. * 2. * PI;
+                                        [
+                                            (center.x + angle.cos() * radius) as f64,
+                                            (center.y + angle.sin() * radius) as f64,
+                                        ]
+                                    })
+                                    .collect(),
+                            ))
+                            .color(Rgba::WHITE)
+                            .name("Brush"),
+                        );
+                        if pointer.primary_down() {
+                            paint_chunk_settings_events.send(PaintChunkSettings {
+                                center,
+                                radius,
+                                falloff: control_center_ui.brush_falloff_slider,
+                                duration: time.delta_seconds(),
+                                spawn_chance: control_center_ui
+                                    .brush_spawn_chance_checkbox
+                                    .then_some(control_center_ui.brush_spawn_chance_drag_value),
+                                spawned_food_energy: control_center_ui
+                                    .brush_food_energy_checkbox
+                                    .then_some(control_center_ui.brush_food_energy_drag_value),
+                                velocity_damping: control_center_ui
+                                    .brush_velocity_damping_checkbox
+                                    .then_some(control_center_ui.brush_velocity_damping_slider),
+                            });
+                        }
+                    }
+                } else if plot_ui.plot_clicked() {
                     // Zelle finden, die am nächsten zu Cursor ist und auswählen
                     let curser_postition = plot_ui.pointer_coordinate().unwrap();
                     let mut nearest_cell_entity = None;
                     let mut nearest_cell_distance_squared = f32::INFINITY;
-                    for (entiy, position, _) in &cell_query {
+                    for (entiy, position, ..) in &cell_query {
                         let relative_position = Position {
                             x: curser_postition.x as f32 - position.x,
                             y: curser_postition.y as f32 - position.y,
@@ -415,6 +1429,8 @@ pub struct StatisticData {
 pub struct StatisticLine {
     pub legend_name: String,
     pub color: [f32; 3],
+    /// Index des ersten Datenpunkts (für Linien, die erst später dazukommen)
+    pub start_index: usize,
     pub data_points: Vec<f32>,
 }
 
@@ -439,6 +1455,20 @@ pub struct CellCountStatistic;
 #[reflect(Component)]
 pub struct BrainSizeStatistic;
 
+#[derive(Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct MutationRateStatistic;
+
+/// Population pro [Species]. Jede Species bekommt eine eigene Linie, sobald es sie gibt.
+#[derive(Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct SpeciesStatistic {
+    /// Id der Species zu jeder Linie (gleiche Reihenfolge wie die Linien in [StatisticData])
+    pub species_ids: Vec<u32>,
+    /// Anzahl an bisher geschriebenen Datenpunkten (Index des nächsten Datenpunkts)
+    pub data_point_count: usize,
+}
+
 pub fn setup_statistics(mut commands: Commands) {
     // Child Count Statistik hinzufügen
     commands.spawn(StatisticBundle {
@@ -448,6 +1478,7 @@ pub fn setup_statistics(mut commands: Commands) {
             lines: vec![StatisticLine {
                 legend_name: "Avg. child count".to_string(),
                 color: [0., 1., 0.],
+                start_index: 0,
                 data_points: Vec::new(),
             }],
         },
@@ -463,16 +1494,19 @@ pub fn setup_statistics(mut commands: Commands) {
                 StatisticLine {
                     legend_name: "Toatal cell count".to_string(),
                     color: [1., 1., 1.],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
                 StatisticLine {
                     legend_name: "Cells born".to_string(),
                     color: [1., 0.5, 0.],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
                 StatisticLine {
                     legend_name: "Cells died".to_string(),
                     color: [1., 0., 0.5],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
             ],
@@ -489,22 +1523,47 @@ pub fn setup_statistics(mut commands: Commands) {
                 StatisticLine {
                     legend_name: "Avg. neuron count".to_string(),
                     color: [0., 1., 0.],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
                 StatisticLine {
                     legend_name: "Avg. connection count".to_string(),
                     color: [0., 1., 0.5],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
                 StatisticLine {
                     legend_name: "Avg. connection count / neuron count".to_string(),
                     color: [1., 1., 1.],
+                    start_index: 0,
                     data_points: Vec::new(),
                 },
             ],
         },
         ..default()
     });
+
+    // Mutation Rate Statistik hinzufügen
+    commands.spawn(StatisticBundle {
+        label: Label("Mutation Rate Statistic".to_string()),
+        unique_tag_component: MutationRateStatistic,
+        data: StatisticData {
+            lines: vec![StatisticLine {
+                legend_name: "Avg. mutation rate".to_string(),
+                color: [1., 0.5, 0.],
+                start_index: 0,
+                data_points: Vec::new(),
+            }],
+        },
+        ..default()
+    });
+
+    // Species Statistik hinzufügen (die Linien werden für jede neue Species in `update_species` angelegt)
+    commands.spawn(StatisticBundle {
+        label: Label("Species Statistic".to_string()),
+        unique_tag_component: SpeciesStatistic::default(),
+        ..default()
+    });
 }
 
 pub fn display_statistics(
@@ -514,14 +1573,22 @@ pub fn display_statistics(
     for (label, mut is_open, data) in &mut statistic_query {
         Window::new(&**label)
             .resizable(true)
-            .open(&mut **is_open)
+            .open(&mut is_open)
             .show(egui_context.ctx_mut(), |ui| {
                 Plot::new("statistic_plot")
                     .legend(default())
                     .show(ui, |plot_ui| {
                         for line in &data.lines {
+                            let points: Vec<[f64; 2]> = line
+                                .data_points
+                                .iter()
+                                .enumerate()
+                                .map(|(index, data_point)| {
+                                    [(line.start_index + index) as f64, *data_point as f64]
+                                })
+                                .collect();
                             plot_ui.line(
-                                Line::new(PlotPoints::from_ys_f32(&line.data_points))
+                                Line::new(PlotPoints::new(points))
                                     .color(Rgba::from_rgb(
                                         line.color[0],
                                         line.color[1],
@@ -543,12 +1610,13 @@ pub struct CellInspectorUi {
 pub fn display_cell_inspector(
     mut egui_context: ResMut<EguiContext>,
     mut cell_inspector_ui: ResMut<CellInspectorUi>,
-    cell_query: Query<(&Brain, &Energy, &CellStats), With<Cell>>,
+    sense_registry: Res<SenseRegistry>,
+    cell_query: Query<(&Brain, &Energy, &CellStats, &Species, &Lineage, &Genome), With<Cell>>,
 ) {
     let Some(selected_cell) = cell_inspector_ui.selected_cell else {
         return
     };
-    let Ok((brain, energy, stats)) = cell_query.get(selected_cell) else {
+    let Ok((brain, energy, stats, species, lineage, genome)) = cell_query.get(selected_cell) else {
         cell_inspector_ui.selected_cell = None;
         return
     };
@@ -569,6 +1637,46 @@ pub fn display_cell_inspector(
                 grid_ui.label("Child count: ");
                 grid_ui.colored_label(Rgba::WHITE, format!("{}", stats.child_count));
                 grid_ui.end_row();
+                grid_ui.label("Species: ");
+                if species.id == 0 {
+                    grid_ui.colored_label(Rgba::WHITE, "-");
+                } else {
+                    grid_ui.colored_label(
+                        Rgba::from_rgb(species.color[0], species.color[1], species.color[2]),
+                        format!("{}", species.id),
+                    );
+                }
+                grid_ui.end_row();
+                grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Lineage -");
+                grid_ui.end_row();
+                grid_ui.label("Id: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{}", lineage.id));
+                grid_ui.end_row();
+                grid_ui.label("Parent id: ");
+                grid_ui.colored_label(
+                    Rgba::WHITE,
+                    lineage
+                        .parent_id
+                        .map_or("-".to_string(), |parent_id| parent_id.to_string()),
+                );
+                grid_ui.end_row();
+                grid_ui.label("Generation: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{}", lineage.generation));
+                grid_ui.end_row();
+                grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Genome -");
+                grid_ui.end_row();
+                grid_ui.label("Size: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{:.2}", genome.size));
+                grid_ui.end_row();
+                grid_ui.label("Speed: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{:.2}", genome.speed));
+                grid_ui.end_row();
+                grid_ui.label("Metabolism: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{:.2}", genome.metabolism));
+                grid_ui.end_row();
+                grid_ui.label("Mutation rate: ");
+                grid_ui.colored_label(Rgba::WHITE, format!("{:.2}", genome.mutation_rate));
+                grid_ui.end_row();
                 grid_ui.colored_label(Rgba::from_rgb(0.145, 0.569, 0.129), "- Brain -");
                 grid_ui.end_row();
             });
@@ -577,25 +1685,22 @@ pub fn display_cell_inspector(
                 .view_aspect(1.)
                 .legend(default())
                 .show(ui, |plot_ui| {
-                    let input_neuron_names = [
-                        "Nearest food angle",
-                        "Nearest food distance",
-                        "Age",
-                        "Energy",
-                        "Oscillator",
-                    ];
-                    let output_neuron_names = ["Rotation", "Acceleration", "Child wish"];
-                    assert_eq!(
-                        (input_neuron_names.len() + output_neuron_names.len()) as u8,
-                        IMMUNE_NEURON_COUNT
-                    );
+                    let input_neuron_names: Vec<&str> = sense_registry
+                        .sensors()
+                        .iter()
+                        .map(|sensor| sensor.name.as_str())
+                        .collect();
+                    let output_neuron_names: Vec<&str> = sense_registry
+                        .actuators()
+                        .iter()
+                        .map(|actuator| actuator.name.as_str())
+                        .collect();
 
                     // Neuronen daten sammeln
                     let mut neuron_positons = Vec::new();
                     for index in 0..brain.neurons().len() {
                         let local_index;
                         let local_len;
-                        let pos_x;
                         let pos_y;
                         if index < input_neuron_names.len() {
                             local_index = index;
@@ -613,7 +1718,7 @@ pub fn display_cell_inspector(
                                 - output_neuron_names.len();
                             pos_y = 0.;
                         }
-                        pos_x = local_index as f64 - local_len as f64 / 2. + 0.5;
+                        let pos_x = local_index as f64 - local_len as f64 / 2. + 0.5;
                         neuron_positons.push([pos_x + (index as f64 * 2.5).cos() * 0.1, pos_y + (index as f64 * 2.5).sin() * 0.25]);
                     }
 